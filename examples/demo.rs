@@ -123,6 +123,6 @@ fn main() {
 
   // WiTui::root_widget(table).alternate(true).run_loop().unwrap();
 
-  let render_ctx = RenderCtx::new(false);
+  let render_ctx = RenderCtx::new(false).unwrap();
   table.render(&render_ctx).unwrap();
 }
@@ -0,0 +1,22 @@
+use std::thread;
+use std::time::Duration;
+
+use witui::WiTui;
+
+/// Example of the inline viewport: the progress is redrawn in place below the prompt,
+/// with the log lines scrolling above it, and the last frame left on screen at the end.
+fn main() {
+  let hosts = ["web-1", "web-2", "db-1"];
+  let mut tui = WiTui::root_widget(format!("Deploying... 0/{}", hosts.len())).inline(1);
+  let handle = tui.handle();
+  thread::spawn(move || {
+    for (idx, host) in hosts.iter().enumerate() {
+      thread::sleep(Duration::from_millis(800));
+      handle.println(format!("deployed {}", host)).ok();
+      let progress = format!("Deploying... {}/{}", idx + 1, hosts.len());
+      handle.update(move |tui| tui.root = Box::new(progress)).ok();
+    }
+    handle.update(|tui| tui.quit()).ok();
+  });
+  tui.run_loop().unwrap();
+}
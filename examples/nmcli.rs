@@ -1,18 +1,11 @@
-use witui::widgets::table::{Column, Table};
-use witui::WiTui;
+use witui::widgets::table::{Column, Table, TableLayout};
+use witui::widgets::Widget;
 
 /// Example to demonstrate the simple use of the Table Widget.
 /// This reproduces the output of nmcli (a linux utility for network management).
-/// output:
-///
-/// DEVICE          TYPE      STATE         CONNECTION
-/// wlp8s0          wifi      connected     SuperRouter
-/// p2p-dev-wlp8s0  wifi-p2p  disconnected  --
-/// enp7s0          ethernet  unavailable   --
-/// lo              loopback  unmanaged     --
-
-fn main() {
-  let table = Table::new()
+/// Expected output is checked in `tests/snapshots/nmcli.snap`.
+pub fn root() -> impl Widget {
+  Table::new()
     .columns(vec![
       Column::new("DEVICE"),
       Column::new("TYPE"),
@@ -24,8 +17,11 @@ fn main() {
       vec!["p2p-dev-wlp8s0", "wifi-p2p", "disconnected", "--"],
       vec!["enp7s0", "ethernet", "unavailable", "--"],
       vec!["lo", "loopback", "unmanaged", "--"],
-    ]);
+    ])
+    .layout(TableLayout::default().column_separator_width(2))
+}
 
-  let mut tui = WiTui::root_widget(table);
-  tui.print().unwrap();
+fn main() {
+  // plain text when piped, e.g. into grep
+  witui::print_widget(&root()).unwrap();
 }
@@ -1,25 +1,11 @@
 use witui::util::Scoped;
 use witui::widgets::table::{Column, ColumnWidth, Row, Table, TableLayout};
-use witui::widgets::Align;
+use witui::widgets::{Align, Widget};
 use witui::WiTui;
 
 /// Spreadsheet example to show off the Table Widget.
-/// output:
-/// ```
-///  A  |  B  |  C  |  D  |  E  |  F  |  G  |  H  |  I  |  J  |  K  |  L  |  M  |  N  |  O  |  P  |  Q  |  R  |  S  |  T  |  U  |  V  |  W  |  X  |  Y  |  Z
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-///     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |     |
-/// ```
-
-fn main() {
+/// Expected output is checked in `tests/snapshots/spreadsheet.snap`.
+pub fn root() -> impl Widget {
   let columns = (b'A'..b'Z' + 1)
     .map(|col| Column::new(Align::center(col as char)).width(ColumnWidth::new().min_fixed(5).flex(0)))
     .collect::<Vec<_>>();
@@ -28,11 +14,13 @@ fn main() {
 
   let data: Vec<Vec<String>> = Vec::new();
 
-  let spreadsheet = Table::new()
+  Table::new()
     .columns(columns)
     .rows(rows)
     .data(data)
-    .layout(TableLayout::default().column_separator('|'));
+    .layout(TableLayout::default().column_separator('|'))
+}
 
-  WiTui::root_widget(spreadsheet).run_loop().unwrap();
+fn main() {
+  WiTui::root_widget(root()).run_loop().unwrap();
 }
@@ -3,6 +3,7 @@ use witui::widgets::{Table, Text, Widget};
 use witui::widgets::table::Column;
 
 fn main() {
+  #[cfg(feature = "logging")]
   witui::enable_pretty_env_logging();
 
   let table = Table::new()
@@ -21,7 +22,7 @@ fn main() {
 
   let root = table;
   let (cols, rows) = crossterm::terminal::size().unwrap();
-  let mut render_ctx = RenderCtx::new(false);
+  let mut render_ctx = RenderCtx::new(false).unwrap();
   root.render(&render_ctx).unwrap();
   render_ctx.renderer().flush();
 }
\ No newline at end of file
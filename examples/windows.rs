@@ -24,7 +24,9 @@ use witui::{Color, HorizontalAlignment, HorizontalSide, Style, VerticalAlignment
 // │                              │
 // ╰──────────────────────────────╯
 
-fn main() {
+/// Window title bar built out of Borders, RowView and Flexible widgets.
+/// Expected output is checked in `tests/snapshots/windows.snap`.
+pub fn root() -> impl Widget {
   let root = Borders::with_child(Expanded::child(()))
     .borders_rounded(Style::default().dark_green())
     .top(Box::new(
@@ -80,10 +82,12 @@ fn main() {
     ) as Box<dyn Widget>);
 
   let root = Min::zero().child(root);
-  let root = Style::new()
+  Style::new()
     .bg(Color::Rgb { r: 20, g: 20, b: 20 })
     .fg(Color::White)
-    .child(root);
+    .child(root)
+}
 
-  WiTui::root_widget(root).alternate(true).run_loop().unwrap();
+fn main() {
+  WiTui::root_widget(root()).alternate(true).run_loop().unwrap();
 }
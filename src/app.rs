@@ -0,0 +1,58 @@
+//! Application layer in the Elm architecture: the app state lives in a model,
+//! changed only by the messages the widgets emit, and the widget tree is a view of it.
+
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::widgets::Widget;
+
+/// An application, run with `WiTui::app`.
+pub trait App: 'static {
+  /// Single source of truth of the application state.
+  type Model: 'static;
+  /// Emitted by the widgets with `EventResult::message`, or sent through a `Handle`.
+  type Msg: 'static;
+
+  fn update(model: &mut Self::Model, msg: Self::Msg);
+  /// Build the widget tree showing the model, called again after each update.
+  fn view(model: &Self::Model) -> impl Widget + 'static;
+}
+
+/// Type-erased app along with its model, owned by the WiTui.
+pub(crate) trait Runner {
+  /// Whether the message is a message of the app.
+  fn accepts(&self, msg: &dyn Any) -> bool;
+  /// Update the model with the message, given it is accepted.
+  fn update(&mut self, msg: Box<dyn Any>);
+  fn view(&self) -> Box<dyn Widget>;
+}
+
+pub(crate) struct AppRunner<A: App> {
+  model: A::Model,
+  app: PhantomData<A>,
+}
+
+impl<A: App> AppRunner<A> {
+  pub(crate) fn new(model: A::Model) -> Self {
+    Self {
+      model,
+      app: PhantomData,
+    }
+  }
+}
+
+impl<A: App> Runner for AppRunner<A> {
+  fn accepts(&self, msg: &dyn Any) -> bool {
+    msg.is::<A::Msg>()
+  }
+
+  fn update(&mut self, msg: Box<dyn Any>) {
+    if let Ok(msg) = msg.downcast::<A::Msg>() {
+      A::update(&mut self.model, *msg);
+    }
+  }
+
+  fn view(&self) -> Box<dyn Widget> {
+    Box::new(A::view(&self.model))
+  }
+}
@@ -0,0 +1,221 @@
+use std::io::{Stdout, Write};
+
+use crossterm::terminal::ClearType;
+use crossterm::{cursor, event, queue, terminal};
+use euclid::default::{Point2D, Size2D};
+
+/// Output device the Renderer draws to.
+///
+/// Only `size`, raw mode, `cursor_position`, `write` and `flush` must be implemented,
+/// the remaining terminal controls default to writing their ANSI escape codes through `write`.
+pub trait Backend {
+  fn size(&self) -> std::io::Result<Size2D<usize>>;
+  fn enable_raw_mode(&mut self) -> std::io::Result<()>;
+  fn disable_raw_mode(&mut self) -> std::io::Result<()>;
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>>;
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<()>;
+  fn flush(&mut self) -> std::io::Result<()>;
+
+  /// Whether the user types in the terminal drawn to, for `run_loop` to read the terminal input.
+  fn reads_terminal_input(&self) -> bool {
+    true
+  }
+
+  fn enter_alternate_screen(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::EnterAlternateScreen)?;
+    self.write(&buf)
+  }
+
+  fn leave_alternate_screen(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::LeaveAlternateScreen)?;
+    self.write(&buf)
+  }
+
+  fn clear(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::Clear(ClearType::All))?;
+    self.write(&buf)
+  }
+
+  /// Clear from the cursor to the end of the screen.
+  fn clear_below(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::Clear(ClearType::FromCursorDown))?;
+    self.write(&buf)
+  }
+
+  fn hide_cursor(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, cursor::Hide)?;
+    self.write(&buf)
+  }
+
+  fn show_cursor(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, cursor::Show)?;
+    self.write(&buf)
+  }
+
+  fn move_cursor(&mut self, pos: Point2D<usize>) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, cursor::MoveTo(pos.x as u16, pos.y as u16))?;
+    self.write(&buf)
+  }
+
+  fn scroll_up(&mut self, rows: usize) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::ScrollUp(rows as u16))?;
+    self.write(&buf)
+  }
+
+  fn enable_line_wrap(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::EnableLineWrap)?;
+    self.write(&buf)
+  }
+
+  fn disable_line_wrap(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, terminal::DisableLineWrap)?;
+    self.write(&buf)
+  }
+
+  fn enable_mouse_capture(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, event::EnableMouseCapture)?;
+    self.write(&buf)
+  }
+
+  fn disable_mouse_capture(&mut self) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    queue!(buf, event::DisableMouseCapture)?;
+    self.write(&buf)
+  }
+}
+
+/// Default backend: crossterm controlling the terminal, with output going to any writer.
+/// A writer that is not the terminal, like a file, is given its size with `with_size`.
+pub struct CrosstermBackend<W: Write> {
+  out: W,
+  /// Size given to a writer that is not the terminal, which is then left alone.
+  size: Option<Size2D<usize>>,
+}
+
+impl CrosstermBackend<Stdout> {
+  pub fn stdout() -> Self {
+    Self::new(std::io::stdout())
+  }
+}
+
+impl<W: Write> CrosstermBackend<W> {
+  pub fn new(out: W) -> Self {
+    Self { out, size: None }
+  }
+
+  /// Backend writing to something other than the terminal, as a screen of the given size.
+  /// The terminal is not queried and raw mode is not entered, the cursor is taken to be at the origin.
+  pub fn with_size(out: W, size: Size2D<usize>) -> Self {
+    Self { out, size: Some(size) }
+  }
+
+  pub fn get_ref(&self) -> &W {
+    &self.out
+  }
+
+  pub fn get_mut(&mut self) -> &mut W {
+    &mut self.out
+  }
+
+  pub fn into_inner(self) -> W {
+    self.out
+  }
+}
+
+impl<W: Write> Backend for CrosstermBackend<W> {
+  fn size(&self) -> std::io::Result<Size2D<usize>> {
+    if let Some(size) = self.size {
+      return Ok(size);
+    }
+    let (cols, rows) = terminal::size()?;
+    Ok(Size2D::new(cols as usize, rows as usize))
+  }
+
+  fn enable_raw_mode(&mut self) -> std::io::Result<()> {
+    match self.size {
+      Some(_) => Ok(()),
+      None => terminal::enable_raw_mode(),
+    }
+  }
+
+  fn disable_raw_mode(&mut self) -> std::io::Result<()> {
+    match self.size {
+      Some(_) => Ok(()),
+      None => terminal::disable_raw_mode(),
+    }
+  }
+
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>> {
+    if self.size.is_some() {
+      return Ok(Point2D::zero());
+    }
+    let (col, row) = cursor::position()?;
+    Ok(Point2D::new(col as usize, row as usize))
+  }
+
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<()> {
+    self.out.write_all(buf)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.out.flush()
+  }
+
+  fn reads_terminal_input(&self) -> bool {
+    self.size.is_none()
+  }
+}
+
+/// In-memory backend of a fixed size that never touches the terminal, for off-screen rendering.
+/// Terminal controls are accepted and the output is discarded,
+/// the rendered frame is to be inspected through the Canvas instead.
+pub struct HeadlessBackend {
+  size: Size2D<usize>,
+}
+
+impl HeadlessBackend {
+  pub fn new(size: Size2D<usize>) -> Self {
+    Self { size }
+  }
+}
+
+impl Backend for HeadlessBackend {
+  fn size(&self) -> std::io::Result<Size2D<usize>> {
+    Ok(self.size)
+  }
+
+  fn enable_raw_mode(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn disable_raw_mode(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>> {
+    Ok(Point2D::zero())
+  }
+
+  fn write(&mut self, _buf: &[u8]) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn reads_terminal_input(&self) -> bool {
+    false
+  }
+}
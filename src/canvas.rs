@@ -10,14 +10,68 @@ use std::io::{BufWriter, Stdout, Write};
 use std::iter::{Map, Zip};
 use std::ops::BitOr;
 
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::backend::Backend;
 use crate::log::{info, trace};
+use crate::util::grapheme_width;
+
+/// Content of a Cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Symbol {
+  #[default]
+  Empty,
+  /// Grapheme cluster, wide ones are followed by a Continuation cell.
+  Grapheme(String),
+  /// Cell covered by the wide grapheme on its left.
+  Continuation,
+}
 
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Cell {
-  data: Option<char>,
+  symbol: Symbol,
   style: ContentStyle,
 }
 
+impl Cell {
+  pub fn symbol(&self) -> &Symbol {
+    &self.symbol
+  }
+
+  /// Grapheme cluster starting at this cell, if any.
+  pub fn data(&self) -> Option<&str> {
+    match &self.symbol {
+      Symbol::Grapheme(grapheme) => Some(grapheme.as_str()),
+      Symbol::Empty | Symbol::Continuation => None,
+    }
+  }
+
+  /// Number of columns the terminal cursor advances when printing this cell.
+  fn width(&self) -> usize {
+    match &self.symbol {
+      Symbol::Empty => 1,
+      Symbol::Grapheme(grapheme) => grapheme_width(grapheme),
+      Symbol::Continuation => 0,
+    }
+  }
+
+  pub fn style(&self) -> &ContentStyle {
+    &self.style
+  }
+
+  pub fn fg(&self) -> Option<Color> {
+    self.style.foreground_color
+  }
+
+  pub fn bg(&self) -> Option<Color> {
+    self.style.background_color
+  }
+
+  pub fn attributes(&self) -> Attributes {
+    self.style.attributes
+  }
+}
+
 pub struct Canvas {
   frame: Rect<usize>,
   draw_buffer: Vec<Vec<Cell>>,   // Rows<Cols<Cells>>
@@ -73,10 +127,107 @@ impl Canvas {
       .for_each(|row| row.resize(size.width, Cell::default()));
   }
 
-  pub(crate) fn write(&mut self, point: &Point2D<usize>, data: &str) {
+  pub fn size(&self) -> Size2D<usize> {
+    self.frame.size
+  }
+
+  /// Cells of the last rendered frame, as Rows<Cols<Cells>>.
+  pub fn cells(&self) -> &Vec<Vec<Cell>> {
+    &self.active_buffer
+  }
+
+  pub fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+    self.active_buffer.get(y).and_then(|row| row.get(x))
+  }
+
+  /// Text of the last rendered frame, one String per row, empty cells as spaces.
+  /// Wide graphemes take their two cells, so each row has the display width of the canvas.
+  pub fn lines(&self) -> Vec<String> {
+    self
+      .active_buffer
+      .iter()
+      .map(|row| {
+        row
+          .iter()
+          .map(|cell| match &cell.symbol {
+            Symbol::Empty => " ",
+            Symbol::Grapheme(grapheme) => grapheme.as_str(),
+            Symbol::Continuation => "",
+          })
+          .collect()
+      })
+      .collect()
+  }
+
+  /// Text of the last rendered frame, each row followed by the runs of styled cells in it:
+  /// ```text
+  ///   0|Hello World |
+  ///    |  0..5   fg=Green bg=- +Bold
+  /// ```
+  pub fn dump(&self) -> String {
+    let mut dump = String::new();
+    for (y, (row, line)) in self.active_buffer.iter().zip(self.lines()).enumerate() {
+      dump.push_str(&format!("{:>3}|{}|\n", y, line));
+      let mut start = 0;
+      while start < row.len() {
+        let style = row[start].style;
+        let end = start + row[start..].iter().take_while(|cell| cell.style == style).count();
+        if style != ContentStyle::default() {
+          dump.push_str(&format!("   |  {}..{}  {}\n", start, end, describe_style(&style)));
+        }
+        start = end;
+      }
+    }
+    dump
+  }
+
+  /// Write the graphemes of the string from the given point on, returns the number of cells written.
+  /// Zero-width graphemes are skipped, and writing stops at the first grapheme not fitting in the row.
+  /// Written cells take the given style, keeping their background color if the style has none.
+  pub(crate) fn write(&mut self, point: &Point2D<usize>, data: &str, style: &ContentStyle) -> usize {
     assert!(self.frame.contains(point.clone()));
-    for (idx, char) in data.chars().take(self.frame.width()).enumerate() {
-      self.draw_buffer[point.y][point.x + idx].data = Some(char);
+    let row = &mut self.draw_buffer[point.y];
+    let mut x = point.x;
+    for grapheme in data.graphemes(true) {
+      let width = grapheme_width(grapheme);
+      if width == 0 {
+        continue;
+      }
+      if x + width > row.len() {
+        break;
+      }
+      Self::clear_wide_overlap(row, x, width);
+      row[x].symbol = Symbol::Grapheme(grapheme.to_string());
+      for cell in row[x + 1..x + width].iter_mut() {
+        cell.symbol = Symbol::Continuation;
+      }
+      for cell in row[x..x + width].iter_mut() {
+        cell.style = ContentStyle {
+          foreground_color: style.foreground_color,
+          background_color: style.background_color.or(cell.style.background_color),
+          attributes: style.attributes,
+        };
+      }
+      x += width;
+    }
+    x - point.x
+  }
+
+  /// Blank out the halves of wide graphemes that are about to be partially overwritten.
+  fn clear_wide_overlap(row: &mut [Cell], x: usize, width: usize) {
+    if let Symbol::Continuation = row[x].symbol {
+      let lead = row[..x].iter().rposition(|cell| cell.symbol != Symbol::Continuation);
+      for cell in row[lead.unwrap_or(0)..x].iter_mut() {
+        cell.symbol = Symbol::Empty;
+      }
+    }
+    let end = x + width;
+    let trail = row[end..]
+      .iter()
+      .take_while(|cell| cell.symbol == Symbol::Continuation)
+      .count();
+    for cell in row[end..end + trail].iter_mut() {
+      cell.symbol = Symbol::Empty;
     }
   }
 
@@ -125,7 +276,42 @@ impl Canvas {
     }
   }
 
-  pub(crate) fn render(&mut self) {
+  /// Copy the cells being drawn in a rect of another canvas to this one, from the given point on.
+  /// Wide graphemes cut by the rect edges are blanked out, and the copied cells keep
+  /// the background color under them if they have none.
+  pub(crate) fn blit(&mut self, src: &Canvas, rect: &Rect<usize>, point: &Point2D<usize>) {
+    assert!(src.frame.contains_rect(rect));
+    assert!(self.frame.contains_rect(&Rect::new(*point, rect.size)));
+    let width = rect.width();
+    for (src_row, y) in src.draw_buffer[rect.y_range()].iter().zip(point.y..) {
+      let row = &mut self.draw_buffer[y];
+      Self::clear_wide_overlap(row, point.x, width);
+      for (idx, cell) in src_row[rect.x_range()].iter().enumerate() {
+        let dest = &mut row[point.x + idx];
+        let cut = match &cell.symbol {
+          Symbol::Continuation => idx == 0,
+          Symbol::Grapheme(grapheme) => idx + grapheme_width(grapheme) > width,
+          Symbol::Empty => false,
+        };
+        dest.symbol = if cut { Symbol::Empty } else { cell.symbol.clone() };
+        dest.style = ContentStyle {
+          background_color: cell.style.background_color.or(dest.style.background_color),
+          ..cell.style
+        };
+      }
+    }
+  }
+
+  /// Draw the last rendered frame again on the next render, all of it.
+  pub(crate) fn redraw(&mut self) {
+    self.draw_buffer = self.active_buffer.clone();
+    self.force_render_once = true;
+  }
+
+  /// Write the changes since the last render to the backend.
+  /// With a top row, the canvas is drawn from that terminal row on, with the cursor moved there first,
+  /// otherwise from wherever the cursor is.
+  pub(crate) fn render(&mut self, backend: &mut dyn Backend, top: Option<usize>) {
     // Pro tip: fastest way to write to stdout is buffering first on a vector, specifically
     // and then, after the render, flush all to stdout at once.
     // BufWriter is not as fast, it seems to flush in batches, and that's perceivable!
@@ -139,7 +325,11 @@ impl Canvas {
       SetForegroundColor(Color::Reset),
       SetAttribute(Attribute::Reset)
     );
-    let mut cursor_pos = Point2D::<usize>::zero();
+    let mut cursor_pos = match top {
+      Some(_) => Point2D::new(usize::MAX, usize::MAX),
+      None => Point2D::<usize>::zero(),
+    };
+    let top = top.unwrap_or(0);
     let mut bg = Color::Reset;
     let mut fg = Color::Reset;
     let mut attributes = Attributes::default();
@@ -161,7 +351,7 @@ impl Canvas {
         if cursor_pos.x != col {
           if cursor_pos.y != row {
             trace!("[{},{}]: MoveTo  ({}, {})", cursor_pos.y, cursor_pos.x, row, col);
-            queue!(stdout, MoveTo(col as u16, row as u16));
+            queue!(stdout, MoveTo(col as u16, (top + row) as u16));
           } else
           // if col > cursor_pos.x + 5
           {
@@ -178,7 +368,7 @@ impl Canvas {
         } else if cursor_pos.y != row {
           // MoveToRow also begins on 1 for some reason
           trace!("[{},{}]: MoveToRow  ({}, {})", cursor_pos.y, cursor_pos.x, row, col);
-          queue!(stdout, MoveToRow(row as u16 + 1));
+          queue!(stdout, MoveToRow((top + row) as u16 + 1));
         }
         cursor_pos.x = col;
         cursor_pos.y = row;
@@ -211,19 +401,24 @@ impl Canvas {
       }
 
       // character
-      if attr_changed || bg_changed || fg_changed || active_cell.data != draw_cell.data || self.force_render_once {
+      if attr_changed || bg_changed || fg_changed || active_cell.symbol != draw_cell.symbol || self.force_render_once {
         // trace!(
         //   "[{},{}]: char: {}       changed: attr {}, bg: {}, fg: {}, diff: {}",
         //   row,
         //   col,
-        //   draw_cell.data.unwrap_or(' '),
+        //   draw_cell.data().unwrap_or(" "),
         //   attr_changed,
         //   bg_changed,
         //   fg_changed,
-        //   active_cell.data != draw_cell.data
+        //   active_cell.symbol != draw_cell.symbol
         // );
         print_char = true;
-        active_cell.data = draw_cell.data.clone();
+        active_cell.symbol = draw_cell.symbol.clone();
+      }
+
+      // the terminal already covered this cell when printing the wide grapheme on its left
+      if let Symbol::Continuation = draw_cell.symbol {
+        print_char = false;
       }
 
       if print_char && draw_cell.style.attributes != attributes {
@@ -261,9 +456,9 @@ impl Canvas {
 
       if print_char {
         update_cursor(&mut stdout);
-        trace!("[{},{}]: char: {}", row, col, draw_cell.data.unwrap_or(' '));
-        queue!(stdout, Print(draw_cell.data.unwrap_or(' ')));
-        cursor_pos.x += 1;
+        trace!("[{},{}]: char: {}", row, col, draw_cell.data().unwrap_or(" "));
+        queue!(stdout, Print(draw_cell.data().unwrap_or(" ")));
+        cursor_pos.x += draw_cell.width();
       }
 
       // cursor move
@@ -283,10 +478,23 @@ impl Canvas {
     // queue!(stdout, MoveTo(0, 0));
 
     // stdout.flush();
-    std::io::stdout().write_all(stdout.as_slice());
-    std::io::stdout().flush();
+    backend.write(stdout.as_slice()).ok();
+    backend.flush().ok();
 
     // TODO: Temporary:
     self.force_render_once = false;
   }
 }
+
+pub(crate) fn describe_style(style: &ContentStyle) -> String {
+  let color = |color: Option<Color>| color.map(|c| format!("{:?}", c)).unwrap_or_else(|| "-".to_string());
+  let mut desc = format!(
+    "fg={} bg={}",
+    color(style.foreground_color),
+    color(style.background_color)
+  );
+  for attribute in Attribute::iterator().filter(|attr| style.attributes.has(*attr)) {
+    desc.push_str(&format!(" +{:?}", attribute));
+  }
+  desc
+}
@@ -0,0 +1,399 @@
+//! Rendering a widget once into text, for non-interactive output such as scripts and pipes,
+//! and exporting rendered frames to HTML and SVG, for screenshots.
+
+use std::fmt::Write as _;
+use std::io::{IsTerminal, Write};
+
+use crossterm::style::{
+  Attribute, Color, ContentStyle, SetAttribute, SetAttributes, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::{terminal, Command};
+use euclid::default::Size2D;
+
+use crate::canvas::{Canvas, Cell, Symbol};
+use crate::render::RenderCtx;
+use crate::widgets::{RenderError, RenderResult, Widget};
+
+/// Height the widgets are laid out in, those filling all of it are given a single row instead.
+const MAX_HEIGHT: usize = u16::MAX as usize;
+/// Width of the output when it is not a terminal and the terminal size is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Colors of the cells with none, those of xterm.
+const DEFAULT_FG: &str = "#e5e5e5";
+const DEFAULT_BG: &str = "#000000";
+/// The 16 colors of xterm, in the order of the ANSI color values.
+const PALETTE: [&str; 16] = [
+  "#000000", "#cd0000", "#00cd00", "#cdcd00", "#0000ee", "#cd00cd", "#00cdcd", "#e5e5e5", "#7f7f7f", "#ff0000",
+  "#00ff00", "#ffff00", "#5c5cff", "#ff00ff", "#00ffff", "#ffffff",
+];
+
+/// Size of a cell in the SVG, in pixels.
+const SVG_CELL_WIDTH: usize = 9;
+const SVG_CELL_HEIGHT: usize = 18;
+const SVG_FONT_SIZE: usize = 15;
+/// Offset of the text baseline from the top of its row.
+const SVG_BASELINE: usize = 14;
+
+/// Render the widget as plain text, at the given width and the height it needs.
+/// Each row is followed by a newline and has its trailing blanks trimmed.
+pub fn render_to_string<W: Widget + ?Sized>(widget: &W, width: usize) -> Result<String, RenderError> {
+  let ctx = render(widget, width)?;
+  let canvas = ctx.canvas();
+  let mut text = String::new();
+  for line in canvas.lines() {
+    text.push_str(line.trim_end());
+    text.push('\n');
+  }
+  Ok(text)
+}
+
+/// Same as `render_to_string` with the styles kept as ANSI escape codes, reset at the end of each row.
+pub fn render_to_ansi_string<W: Widget + ?Sized>(widget: &W, width: usize) -> Result<String, RenderError> {
+  let ctx = render(widget, width)?;
+  let canvas = ctx.canvas();
+  let mut text = String::new();
+  for row in canvas.cells() {
+    write_ansi_row(&mut text, trim_row(row));
+    text.push('\n');
+  }
+  Ok(text)
+}
+
+/// Print the widget to stdout at the terminal width, with ANSI styles only if stdout is a terminal.
+/// Unlike `WiTui::print`, the terminal is left untouched, so it also works with stdout piped.
+pub fn print_widget<W: Widget + ?Sized>(widget: &W) -> RenderResult {
+  let width = terminal::size().map_or(DEFAULT_WIDTH, |(cols, _)| cols as usize);
+  let stdout = std::io::stdout();
+  let text = if stdout.is_terminal() {
+    render_to_ansi_string(widget, width)?
+  } else {
+    render_to_string(widget, width)?
+  };
+  // a closed pipe is not worth failing for, the reader just did not want more
+  stdout.lock().write_all(text.as_bytes()).ok();
+  Ok(())
+}
+
+/// Same as `render_to_string` as an HTML `<pre>`, see `Canvas::to_html`.
+pub fn render_to_html<W: Widget + ?Sized>(widget: &W, width: usize) -> Result<String, RenderError> {
+  let ctx = render(widget, width)?;
+  let html = ctx.canvas().to_html();
+  Ok(html)
+}
+
+/// Same as `render_to_string` as an SVG image, see `Canvas::to_svg`.
+pub fn render_to_svg<W: Widget + ?Sized>(widget: &W, width: usize) -> Result<String, RenderError> {
+  let ctx = render(widget, width)?;
+  let svg = ctx.canvas().to_svg();
+  Ok(svg)
+}
+
+impl Canvas {
+  /// Last rendered frame as a standalone HTML `<pre>`, each run of styled cells in a `<span>` with inline styles.
+  pub fn to_html(&self) -> String {
+    let mut html = format!(
+      "<pre style=\"font-family:monospace;color:{};background-color:{}\">",
+      DEFAULT_FG, DEFAULT_BG
+    );
+    for (y, row) in self.cells().iter().enumerate() {
+      if y > 0 {
+        html.push('\n');
+      }
+      for run in runs(row) {
+        let text = escape(&run.text);
+        if run.style == ContentStyle::default() {
+          html.push_str(&text);
+          continue;
+        }
+        let look = Look::of(&run.style);
+        let mut css = String::new();
+        if look.fg != DEFAULT_FG {
+          write!(css, "color:{};", look.fg).ok();
+        }
+        if let Some(bg) = &look.bg {
+          write!(css, "background-color:{};", bg).ok();
+        }
+        for (property, value) in look.font() {
+          write!(css, "{}:{};", property, value).ok();
+        }
+        write!(html, "<span style=\"{}\">{}</span>", css.trim_end_matches(';'), text).ok();
+      }
+    }
+    html.push_str("</pre>\n");
+    html
+  }
+
+  /// Last rendered frame as an SVG image, the backgrounds drawn as rects under monospace texts,
+  /// each text stretched over its cells to stay on the grid whatever the font.
+  pub fn to_svg(&self) -> String {
+    let size = self.size();
+    let (width, height) = (size.width * SVG_CELL_WIDTH, size.height * SVG_CELL_HEIGHT);
+    let mut svg = format!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+      w = width,
+      h = height
+    );
+    writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", DEFAULT_BG).ok();
+    // all the backgrounds go first, for the texts stretched past their cells not to be covered
+    let runs: Vec<(usize, Run)> = self
+      .cells()
+      .iter()
+      .enumerate()
+      .flat_map(|(y, row)| runs(row).into_iter().map(move |run| (y, run)))
+      .collect();
+    for (y, run) in &runs {
+      if let Some(bg) = Look::of(&run.style).bg {
+        writeln!(
+          svg,
+          "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+          run.x * SVG_CELL_WIDTH,
+          y * SVG_CELL_HEIGHT,
+          run.width * SVG_CELL_WIDTH,
+          SVG_CELL_HEIGHT,
+          bg
+        )
+        .ok();
+      }
+    }
+    writeln!(
+      svg,
+      "<g font-family=\"monospace\" font-size=\"{}px\" fill=\"{}\" xml:space=\"preserve\">",
+      SVG_FONT_SIZE, DEFAULT_FG
+    )
+    .ok();
+    for (y, run) in &runs {
+      let look = Look::of(&run.style);
+      let decorated = look.font().iter().any(|(property, _)| *property == "text-decoration");
+      if run.text.trim().is_empty() && !decorated {
+        continue;
+      }
+      let mut attributes = String::new();
+      if look.fg != DEFAULT_FG {
+        write!(attributes, " fill=\"{}\"", look.fg).ok();
+      }
+      for (property, value) in look.font() {
+        let property = if property == "opacity" { "fill-opacity" } else { property };
+        write!(attributes, " {}=\"{}\"", property, value).ok();
+      }
+      writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\"{}>{}</text>",
+        run.x * SVG_CELL_WIDTH,
+        y * SVG_CELL_HEIGHT + SVG_BASELINE,
+        run.width * SVG_CELL_WIDTH,
+        attributes,
+        escape(&run.text)
+      )
+      .ok();
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+  }
+}
+
+/// Cells of a row in the same style.
+struct Run {
+  style: ContentStyle,
+  /// First column.
+  x: usize,
+  /// Number of columns.
+  width: usize,
+  text: String,
+}
+
+fn runs(row: &[Cell]) -> Vec<Run> {
+  let mut runs: Vec<Run> = Vec::new();
+  for (x, cell) in row.iter().enumerate() {
+    let text = match cell.symbol() {
+      Symbol::Empty => " ",
+      Symbol::Grapheme(grapheme) => grapheme.as_str(),
+      Symbol::Continuation => "",
+    };
+    match runs.last_mut() {
+      Some(run) if run.style == *cell.style() => {
+        run.width += 1;
+        run.text.push_str(text);
+      }
+      _ => runs.push(Run {
+        style: *cell.style(),
+        x,
+        width: 1,
+        text: text.to_string(),
+      }),
+    }
+  }
+  runs
+}
+
+/// How a style looks on the exports, with the reversed and hidden cells resolved.
+struct Look {
+  fg: String,
+  /// None for the default background, already painted.
+  bg: Option<String>,
+  attributes: crossterm::style::Attributes,
+}
+
+impl Look {
+  fn of(style: &ContentStyle) -> Self {
+    let mut fg = style
+      .foreground_color
+      .and_then(hex)
+      .unwrap_or_else(|| DEFAULT_FG.to_string());
+    let mut bg = style.background_color.and_then(hex);
+    if style.attributes.has(Attribute::Reverse) {
+      let reversed_bg = Some(fg);
+      fg = bg.unwrap_or_else(|| DEFAULT_BG.to_string());
+      bg = reversed_bg;
+    }
+    if style.attributes.has(Attribute::Hidden) {
+      fg = bg.clone().unwrap_or_else(|| DEFAULT_BG.to_string());
+    }
+    Self {
+      fg,
+      bg,
+      attributes: style.attributes,
+    }
+  }
+
+  /// CSS properties of the attributes, also valid as SVG attributes.
+  fn font(&self) -> Vec<(&'static str, &'static str)> {
+    let mut font = Vec::new();
+    if self.attributes.has(Attribute::Bold) {
+      font.push(("font-weight", "bold"));
+    }
+    if self.attributes.has(Attribute::Dim) {
+      font.push(("opacity", "0.5"));
+    }
+    if self.attributes.has(Attribute::Italic) {
+      font.push(("font-style", "italic"));
+    }
+    match (
+      self.attributes.has(Attribute::Underlined),
+      self.attributes.has(Attribute::CrossedOut),
+    ) {
+      (true, true) => font.push(("text-decoration", "underline line-through")),
+      (true, false) => font.push(("text-decoration", "underline")),
+      (false, true) => font.push(("text-decoration", "line-through")),
+      (false, false) => {}
+    }
+    font
+  }
+}
+
+/// Hex code of the color, None for the default one.
+fn hex(color: Color) -> Option<String> {
+  let ansi = match color {
+    Color::Reset => return None,
+    Color::Rgb { r, g, b } => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+    Color::AnsiValue(value) => value,
+    Color::Black => 0,
+    Color::DarkRed => 1,
+    Color::DarkGreen => 2,
+    Color::DarkYellow => 3,
+    Color::DarkBlue => 4,
+    Color::DarkMagenta => 5,
+    Color::DarkCyan => 6,
+    Color::Grey => 7,
+    Color::DarkGrey => 8,
+    Color::Red => 9,
+    Color::Green => 10,
+    Color::Yellow => 11,
+    Color::Blue => 12,
+    Color::Magenta => 13,
+    Color::Cyan => 14,
+    Color::White => 15,
+  };
+  let hex = match ansi {
+    0..=15 => PALETTE[ansi as usize].to_string(),
+    // 6x6x6 color cube
+    16..=231 => {
+      let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+      let idx = ansi - 16;
+      format!(
+        "#{:02x}{:02x}{:02x}",
+        level(idx / 36),
+        level(idx / 6 % 6),
+        level(idx % 6)
+      )
+    }
+    // grayscale ramp
+    232..=255 => {
+      let level = 8 + (ansi - 232) * 10;
+      format!("#{:02x}{:02x}{:02x}", level, level, level)
+    }
+  };
+  Some(hex)
+}
+
+/// Text with the characters special to HTML and XML escaped.
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+fn render<W: Widget + ?Sized>(widget: &W, width: usize) -> Result<RenderCtx, RenderError> {
+  let width = width.max(1);
+  let layout = widget
+    .layout(&Size2D::new(width, MAX_HEIGHT))
+    .map_err(RenderError::Layout)?;
+  let height = [layout.max.height, layout.min.height]
+    .iter()
+    .copied()
+    .find(|height| *height < MAX_HEIGHT)
+    .unwrap_or(1);
+  let ctx = RenderCtx::headless(Size2D::new(width, height.max(1)));
+  ctx.render_root(widget)?;
+  ctx.renderer().flush();
+  Ok(ctx)
+}
+
+/// Row without its trailing blank and unstyled cells.
+fn trim_row(row: &[Cell]) -> &[Cell] {
+  let blank = |cell: &Cell| {
+    let empty = matches!(cell.symbol(), Symbol::Empty) || cell.data() == Some(" ");
+    empty && *cell.style() == ContentStyle::default()
+  };
+  let len = row.iter().rposition(|cell| !blank(cell)).map_or(0, |idx| idx + 1);
+  &row[..len]
+}
+
+fn write_ansi_row(text: &mut String, row: &[Cell]) {
+  let mut style = ContentStyle::default();
+  for cell in row {
+    if *cell.style() != style {
+      style = *cell.style();
+      write_ansi_style(text, &style);
+    }
+    match cell.symbol() {
+      Symbol::Empty => text.push(' '),
+      Symbol::Grapheme(grapheme) => text.push_str(grapheme),
+      Symbol::Continuation => {}
+    }
+  }
+  if style != ContentStyle::default() {
+    command(text, SetAttribute(Attribute::Reset));
+  }
+}
+
+/// Switch to the style from any other, starting over from a reset.
+fn write_ansi_style(text: &mut String, style: &ContentStyle) {
+  command(text, SetAttribute(Attribute::Reset));
+  if !style.attributes.is_empty() {
+    command(text, SetAttributes(style.attributes));
+  }
+  if let Some(fg) = style.foreground_color {
+    command(text, SetForegroundColor(fg));
+  }
+  if let Some(bg) = style.background_color {
+    command(text, SetBackgroundColor(bg));
+  }
+}
+
+fn command(text: &mut String, command: impl Command) {
+  // writing to a String cannot fail
+  command.write_ansi(text).ok();
+}
@@ -0,0 +1,100 @@
+//! Posting updates into a running WiTui from other threads.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(feature = "tokio")]
+use std::sync::Arc;
+
+#[cfg(feature = "tokio")]
+use tokio::sync::Notify;
+
+use crate::{Event, WiTui};
+
+pub(crate) enum Message {
+  Custom(Box<dyn Any + Send>),
+  Update(Box<dyn FnOnce(&mut WiTui) + Send>),
+  /// Read by the input thread of `run_loop`.
+  Input(std::io::Result<Event>),
+}
+
+/// Channel the handles post to, read by the event loop.
+pub(crate) struct Mailbox {
+  sender: Sender<Message>,
+  pub(crate) receiver: Receiver<Message>,
+  #[cfg(feature = "tokio")]
+  pub(crate) posted: Arc<Notify>,
+}
+
+impl Mailbox {
+  pub(crate) fn new() -> Self {
+    let (sender, receiver) = mpsc::channel();
+    Self {
+      sender,
+      receiver,
+      #[cfg(feature = "tokio")]
+      posted: Default::default(),
+    }
+  }
+
+  pub(crate) fn handle(&self) -> Handle {
+    Handle {
+      sender: self.sender.clone(),
+      #[cfg(feature = "tokio")]
+      posted: self.posted.clone(),
+    }
+  }
+
+  /// Sender for the input thread, `run` reading the input on its own.
+  pub(crate) fn sender(&self) -> Sender<Message> {
+    self.sender.clone()
+  }
+}
+
+/// The WiTui the handle posts to is gone.
+#[derive(Debug)]
+pub struct Disconnected;
+
+impl Display for Disconnected {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Handle error: the WiTui is gone")
+  }
+}
+
+impl Error for Disconnected {}
+
+/// Sendable handle to a WiTui, got from `WiTui::handle`.
+/// What is posted wakes the event loop up and is handled on the UI thread, with a new frame rendered after.
+#[derive(Clone)]
+pub struct Handle {
+  sender: Sender<Message>,
+  /// Wakes `run` up, which can't block on the channel.
+  #[cfg(feature = "tokio")]
+  posted: Arc<Notify>,
+}
+
+impl Handle {
+  /// Send a message to every widget of the tree as `AnyEvent::Custom`.
+  pub fn send<M: Any + Send>(&self, message: M) -> Result<(), Disconnected> {
+    self.post(Message::Custom(Box::new(message)))
+  }
+
+  /// Run the closure with the WiTui, to mutate the widget tree.
+  pub fn update<F: FnOnce(&mut WiTui) + Send + 'static>(&self, update: F) -> Result<(), Disconnected> {
+    self.post(Message::Update(Box::new(update)))
+  }
+
+  /// Print a line above the inline viewport, see `WiTui::println`.
+  pub fn println<L: Into<String>>(&self, line: L) -> Result<(), Disconnected> {
+    let line = line.into();
+    self.update(move |tui| tui.println(&line))
+  }
+
+  fn post(&self, message: Message) -> Result<(), Disconnected> {
+    self.sender.send(message).map_err(|_| Disconnected)?;
+    #[cfg(feature = "tokio")]
+    self.posted.notify_one();
+    Ok(())
+  }
+}
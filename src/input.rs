@@ -0,0 +1,54 @@
+//! Reading the terminal input on a thread of its own, for `run_loop` to wait on the input and the handles at once.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::handle::Message;
+
+/// How long the thread waits for input before checking whether it is to stop.
+const STOP_POLL: Duration = Duration::from_millis(50);
+
+/// Thread posting each input event to the channel of the handles, stopped once dropped.
+/// It ends on its own after posting a read error.
+pub(crate) struct InputThread {
+  stop: Arc<AtomicBool>,
+  thread: Option<JoinHandle<()>>,
+}
+
+impl InputThread {
+  pub(crate) fn spawn(sender: Sender<Message>) -> Self {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = stop.clone();
+    let thread = thread::spawn(move || {
+      while !stopped.load(Ordering::Relaxed) {
+        let event = match crossterm::event::poll(STOP_POLL) {
+          Ok(false) => continue,
+          // left for whoever reads the input next
+          Ok(true) if stopped.load(Ordering::Relaxed) => break,
+          Ok(true) => crossterm::event::read(),
+          Err(error) => Err(error),
+        };
+        let failed = event.is_err();
+        if sender.send(Message::Input(event)).is_err() || failed {
+          break;
+        }
+      }
+    });
+    Self {
+      stop,
+      thread: Some(thread),
+    }
+  }
+}
+
+impl Drop for InputThread {
+  fn drop(&mut self) {
+    self.stop.store(true, Ordering::Relaxed);
+    if let Some(thread) = self.thread.take() {
+      thread.join().ok();
+    }
+  }
+}
@@ -1,32 +1,79 @@
 extern crate euclid;
 
 use std::any::Any;
+use std::cell::{Ref, RefCell};
 use std::io::Write;
-use std::time::Duration;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 pub use crossterm::event::{Event, KeyCode, KeyModifiers, MouseEventKind};
 pub use crossterm::style::{Attribute, Attributes, Color};
+use crossterm::style::ContentStyle;
+use euclid::default::Size2D;
 
 #[cfg(feature = "logging")]
 pub use crate::log::enable_pretty_env_logging;
+pub use crate::export::{print_widget, render_to_ansi_string, render_to_html, render_to_string, render_to_svg};
+use crate::app::{App, AppRunner, Runner};
+use crate::backend::{Backend, CrosstermBackend};
+use crate::handle::{Handle, Mailbox, Message};
+use crate::input::InputThread;
+use crate::pointer::Pointer;
+use crate::record::{Recorder, RecordingBackend};
 use crate::render::RenderCtx;
+use crate::timer::Timers;
+use crate::tree::{Focus, Frames};
 use crate::util::{Scoped, ScopedMut};
-use crate::widgets::{AnyEvent, LayoutError, RenderResult, Styled, Widget};
+use crate::widgets::{AnyEvent, EventResult, LayoutError, RenderError, RenderResult, Styled, Widget, WidgetId};
 
 #[macro_use]
 pub(crate) mod log;
 
+pub mod app;
+pub mod backend;
 pub mod canvas;
+mod export;
+pub mod handle;
+mod input;
+pub(crate) mod pointer;
+pub mod record;
 pub mod render;
+#[cfg(feature = "tokio")]
+mod runtime;
+pub mod testing;
+pub mod timer;
+pub mod tree;
 pub mod util;
 pub mod widgets;
 
+/// Used by the code the derive macros generate.
+#[doc(hidden)]
+pub mod __private {
+  pub use euclid::default::Size2D;
+}
+
 // TODO: Make all Widgets divisible between property and widget so we can have (along with WidgetExt):
 // let widget = "Hello".bordered(Borders::lines());
 
 pub struct WiTui {
   alternate: bool,
-  render_ctx: RenderCtx,
+  inline: Option<usize>,
+  quitting: bool,
+  backend: Option<Box<dyn Backend>>,
+  /// The backend was consumed by a failed attempt at creating the render context.
+  setup_failed: bool,
+  render_ctx: Option<RenderCtx>,
+  focus: Focus,
+  pointer: Pointer,
+  timers: Timers,
+  tick_rate: Option<Duration>,
+  next_tick: Option<Instant>,
+  mailbox: Mailbox,
+  #[cfg(feature = "tokio")]
+  streams: runtime::Streams,
+  app: Option<Box<dyn Runner>>,
+  recorder: Option<Rc<RefCell<Recorder>>>,
   pub root: Box<dyn Widget>,
 }
 
@@ -34,39 +81,157 @@ impl WiTui {
   pub fn root_widget<W: Widget + 'static>(root: W) -> Self {
     Self {
       alternate: false,
-      render_ctx: RenderCtx::new(false),
+      inline: None,
+      quitting: false,
+      backend: None,
+      setup_failed: false,
+      render_ctx: None,
+      focus: Focus::default(),
+      pointer: Pointer::default(),
+      timers: Timers::new(),
+      tick_rate: None,
+      next_tick: None,
+      mailbox: Mailbox::new(),
+      #[cfg(feature = "tokio")]
+      streams: Default::default(),
+      app: None,
+      recorder: None,
       root: Box::new(root) as Box<dyn Widget>,
     }
   }
 
+  /// Run an app, the root widget being its view of the model.
+  pub fn app<A: App>(model: A::Model) -> Self {
+    let app = AppRunner::<A>::new(model);
+    let mut tui = Self::root_widget(app.view());
+    tui.app = Some(Box::new(app));
+    tui
+  }
+
   pub fn alternate(mut self, alternate: bool) -> Self {
-    // temporary hack to recreate renderer
-    drop(self.render_ctx);
-    Self {
-      alternate,
-      render_ctx: RenderCtx::new(alternate),
-      root: self.root,
+    self.alternate = alternate;
+    if alternate {
+      self.inline = None;
     }
+    self
+  }
+
+  /// Draw in a viewport of that many lines below the prompt instead of the whole screen,
+  /// redrawn in place on each frame of `run_loop`. The last frame is left on screen once the WiTui is dropped.
+  pub fn inline(mut self, height: usize) -> Self {
+    self.inline = Some(height);
+    self.alternate = false;
+    self
+  }
+
+  /// Send `AnyEvent::Tick` to every widget at this rate, with a new frame after each tick.
+  pub fn tick_rate(mut self, tick_rate: Duration) -> Self {
+    self.tick_rate = Some(tick_rate);
+    self
+  }
+
+  /// Handle to schedule callbacks on the event loop.
+  pub fn timers(&self) -> Timers {
+    self.timers.clone()
+  }
+
+  /// Sendable handle for posting messages and updates from other threads.
+  pub fn handle(&self) -> Handle {
+    self.mailbox.handle()
+  }
+
+  /// The widget given the ID by an `Id`, if there is one of type T.
+  /// Widgets in children that containers build on the fly, see `Widget::get_child`, are not found.
+  pub fn find<T: 'static>(&self, id: impl Into<WidgetId>) -> Option<&T> {
+    let path = tree::find(self.root.as_ref(), &id.into())?;
+    tree::widget(self.root.as_ref(), &path)?.as_any()?.downcast_ref()
+  }
+
+  /// Same as `find`, with the widget lent mutably.
+  pub fn find_mut<T: 'static>(&mut self, id: impl Into<WidgetId>) -> Option<&mut T> {
+    let path = tree::find(self.root.as_ref(), &id.into())?;
+    tree::widget_mut(self.root.as_mut(), &path)?.as_any_mut()?.downcast_mut()
+  }
+
+  /// Render to the given backend instead of the crossterm stdout default.
+  pub fn backend<B: Backend + 'static>(mut self, backend: B) -> Self {
+    self.backend = Some(Box::new(backend));
+    self
+  }
+
+  /// The render context is only created on the first render,
+  /// so that the builder methods do not take over the terminal before the backend is settled.
+  /// A backend that failed to set up the terminal is not replaced by the default one on later calls.
+  fn render_ctx(&mut self) -> std::io::Result<&mut RenderCtx> {
+    if self.render_ctx.is_none() {
+      if self.setup_failed {
+        return Err(std::io::Error::other("the backend failed to set up"));
+      }
+      self.setup_failed = true;
+      let mut backend = self.backend.take().unwrap_or_else(|| Box::new(CrosstermBackend::stdout()));
+      if let Some(recorder) = &self.recorder {
+        backend = Box::new(RecordingBackend::new(backend, recorder.clone()));
+      }
+      self.render_ctx = Some(match self.inline {
+        Some(height) => RenderCtx::inline(backend, height)?,
+        None => RenderCtx::with_boxed_backend(backend, self.alternate)?,
+      });
+      self.setup_failed = false;
+    }
+    Ok(self.render_ctx.as_mut().unwrap())
+  }
+
+  /// Whether the event loop runs, on the alternate screen or the inline viewport,
+  /// rather than printing a single frame.
+  fn interactive(&self) -> bool {
+    self.alternate || self.inline.is_some()
   }
 
   pub fn print(&mut self) -> RenderResult {
-    let result = self.root.render(&mut self.render_ctx);
-    self.render_ctx.renderer().flush();
+    let size = self.render_ctx().map_err(RenderError::Io)?.get_frame().size;
+    let interactive = self.interactive();
+    let render_ctx = self.render_ctx.as_mut().unwrap();
+    if interactive {
+      self.focus.ensure(self.root.as_mut(), &render_ctx.frames(), &size);
+    }
+    let result = render_ctx.render_root(self.root.as_ref());
+    render_ctx.renderer().flush();
     result
   }
 
-  pub fn quit(mut self) {}
+  /// Print a line above the inline viewport, that scrolls up along with the terminal content.
+  /// Ignored outside of the inline viewport, where there is no room above.
+  pub fn println(&mut self, line: &str) {
+    if let Ok(render_ctx) = self.render_ctx() {
+      render_ctx.renderer().println(line);
+    }
+  }
+
+  /// End the event loop, once the current frame is rendered.
+  pub fn quit(&mut self) {
+    self.quitting = true;
+  }
 
   // TODO: Compute FPS, ms/frame
   //  Provide API with rendering info, for displaying in a widget
+  /// Fails right away when the terminal can't be set up or its input can't be read,
+  /// render errors of the frames are not reported.
+  /// The terminal input is read only when the backend draws to the terminal, see `Backend::reads_terminal_input`.
   pub fn run_loop(&mut self) -> RenderResult {
+    let reads_input = self.render_ctx().map_err(RenderError::Io)?.renderer().reads_terminal_input();
+    let mut input = None;
     loop {
-      self.print();
-      if !self.alternate {
+      // a frame that fails to render, on a terminal too small for it for instance, is skipped
+      self.print().ok();
+      if !self.interactive() || std::mem::take(&mut self.quitting) {
         break;
       }
+      // read on a thread of its own, for the loop to wait on the input and the handles at once
+      if reads_input && input.is_none() {
+        input = Some(InputThread::spawn(self.mailbox.sender()));
+      }
       let mut quit = false;
-      self.event_loop(&mut quit);
+      self.event_loop(&mut quit).map_err(RenderError::Io)?;
       if quit {
         break;
       }
@@ -74,44 +239,162 @@ impl WiTui {
     Ok(())
   }
 
-  fn event_loop(&mut self, quit: &mut bool) {
+  /// Wait for input and for the handles until the next tick or timer is due, returns once a new frame is needed.
+  fn event_loop(&mut self, quit: &mut bool) -> std::io::Result<()> {
     loop {
-      match crossterm::event::read().unwrap() {
-        Event::Key(key) => match key.code {
-          KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => {
-            *quit = true;
-            break;
-          }
-          _ => {
-            self
-              .root
-              .event(&AnyEvent::Input(Event::Key(key)), &self.render_ctx.get_frame().size);
-            break;
-          }
-        },
-        Event::Mouse(mouse) => {
-          if let MouseEventKind::Down(_) = mouse.kind {
-            self
-              .root
-              .event(&AnyEvent::Input(Event::Mouse(mouse)), &self.render_ctx.get_frame().size);
-            break;
-          }
+      let received = match self.poll_timeout() {
+        Some(timeout) => self.mailbox.receiver.recv_timeout(timeout).ok(),
+        // the mailbox keeps a sender, the channel can't disconnect
+        None => self.mailbox.receiver.recv().ok(),
+      };
+      let mut posted = false;
+      if let Some(message) = received {
+        posted = self.handle_message(message, quit)?;
+      }
+      let due = self.handle_deadlines(Instant::now());
+      let posted = posted | self.handle_messages(quit)?;
+      if due || posted || self.quitting || *quit {
+        return Ok(());
+      }
+    }
+  }
+
+  /// Handle what was posted through the handles and the input read meanwhile,
+  /// returns whether anything needs a new frame.
+  pub(crate) fn handle_messages(&mut self, quit: &mut bool) -> std::io::Result<bool> {
+    let mut handled = false;
+    while let Ok(message) = self.mailbox.receiver.try_recv() {
+      handled |= self.handle_message(message, quit)?;
+      if *quit {
+        break;
+      }
+    }
+    Ok(handled)
+  }
+
+  /// Handle one message, returns whether it needs a new frame. An error reading the input is returned.
+  pub(crate) fn handle_message(&mut self, message: Message, quit: &mut bool) -> std::io::Result<bool> {
+    match message {
+      Message::Custom(message) if self.accepts(message.as_ref()) => {
+        self.update_app(message);
+      }
+      Message::Custom(message) => {
+        if let Some((size, frames)) = routing(&self.render_ctx) {
+          tree::broadcast(self.root.as_mut(), &AnyEvent::Custom(message), &frames, &size);
         }
-        Event::Resize(cols, rows) => {
-          // let (original_size, new_size) = flush_resize_events(Event::Resize(cols, rows));
-          // let (cols, rows) = new_size;
-          // eprintln!("Resize from: {:?}, to: {:?}", original_size, new_size);
-          if self.alternate {
-            self.render_ctx.resize(cols as usize, rows as usize);
-            self.render_ctx.renderer().force_render_once(); // TEMPORARY: just for resize (URXVT BUG)
-            break; // TODO: uncomment
+      }
+      Message::Update(update) => update(self),
+      Message::Input(event) => return Ok(self.handle_event(event?, quit).is_some()),
+    }
+    Ok(true)
+  }
+
+  /// How long to wait before the next tick or timer is due, none if neither is.
+  fn poll_timeout(&mut self) -> Option<Duration> {
+    self
+      .next_deadline()
+      .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+  }
+
+  fn next_deadline(&mut self) -> Option<Instant> {
+    if let Some(tick_rate) = self.tick_rate {
+      self.next_tick.get_or_insert_with(|| Instant::now() + tick_rate);
+    }
+    match (self.next_tick, self.timers.next_deadline()) {
+      (Some(tick), Some(timer)) => Some(tick.min(timer)),
+      (tick, timer) => tick.or(timer),
+    }
+  }
+
+  /// Run the tick and the timers due at the given instant, returns whether any did and a new frame is needed.
+  pub(crate) fn handle_deadlines(&mut self, now: Instant) -> bool {
+    self.next_deadline();
+    let mut due = self.timers.fire_due(now) > 0;
+    if let (Some(tick_rate), Some(next_tick)) = (self.tick_rate, self.next_tick) {
+      if next_tick <= now {
+        if let Some((size, frames)) = routing(&self.render_ctx) {
+          tree::broadcast(self.root.as_mut(), &AnyEvent::Tick, &frames, &size);
+        }
+        self.next_tick = Some((next_tick + tick_rate).max(now));
+        due = true;
+      }
+    }
+    due
+  }
+
+  /// Whether there is an app taking the message.
+  fn accepts(&self, message: &dyn Any) -> bool {
+    self.app.as_ref().is_some_and(|app| app.accepts(message))
+  }
+
+  /// Update the app with the message and rebuild the view.
+  fn update_app(&mut self, message: Box<dyn Any>) {
+    if let Some(app) = self.app.as_mut() {
+      app.update(message);
+      self.root = app.view();
+    }
+    if !self.interactive() {
+      return;
+    }
+    if let Some((size, frames)) = routing(&self.render_ctx) {
+      self.focus.refocus(self.root.as_mut(), &frames, &size);
+    }
+  }
+
+  /// Dispatch one input event, returns None if the event was dropped and no new frame is needed.
+  /// A message emitted for the app is handed over to it, and the result is then Done.
+  pub(crate) fn handle_event(&mut self, event: Event, quit: &mut bool) -> Option<EventResult> {
+    if let Some(recorder) = &self.recorder {
+      recorder.borrow_mut().input(&event);
+    }
+    match self.dispatch_event(event, quit) {
+      Some(EventResult::Message(message)) if self.accepts(message.as_ref()) => {
+        self.update_app(message);
+        Some(EventResult::Done)
+      }
+      result => result,
+    }
+  }
+
+  fn dispatch_event(&mut self, event: Event, quit: &mut bool) -> Option<EventResult> {
+    match event {
+      Event::Key(key) => match key.code {
+        KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => {
+          *quit = true;
+          None
+        }
+        _ => {
+          let (size, frames) = routing(&self.render_ctx)?;
+          Some(self.focus.dispatch_key(self.root.as_mut(), key, &frames, &size))
+        }
+      },
+      Event::Mouse(mouse) => {
+        let (size, frames) = routing(&self.render_ctx)?;
+        Some(self.pointer.dispatch(self.root.as_mut(), &frames, mouse, &size, self.timers.now()))
+      }
+      Event::Resize(cols, rows) => {
+        // let (original_size, new_size) = flush_resize_events(Event::Resize(cols, rows));
+        // let (cols, rows) = new_size;
+        // eprintln!("Resize from: {:?}, to: {:?}", original_size, new_size);
+        let interactive = self.interactive();
+        match self.render_ctx.as_mut() {
+          Some(render_ctx) if interactive => {
+            render_ctx.resize(cols as usize, rows as usize);
+            render_ctx.renderer().force_render_once(); // TEMPORARY: just for resize (URXVT BUG)
+            Some(EventResult::Unhandled)
           }
+          _ => None,
         }
       }
     }
   }
 }
 
+/// Size of the screen and frames of the last render the events are routed by, none before the first render.
+fn routing(render_ctx: &Option<RenderCtx>) -> Option<(Size2D<usize>, Ref<'_, Frames>)> {
+  render_ctx.as_ref().map(|ctx| (ctx.get_frame().size, ctx.frames()))
+}
+
 // Resize events can occur in batches.
 // With a simple loop they can be flushed.
 // This function will keep the first and last resize event.
@@ -134,6 +417,37 @@ pub trait ChildrenStorage: 'static {
   fn child_mut(&mut self, index: usize) -> Option<ScopedMut<dyn Widget>>;
   fn as_any(&self) -> &dyn Any;
   fn as_any_mut(&mut self) -> &mut dyn Any;
+
+  fn visit(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    for idx in 0..self.len() {
+      if let Some(child) = self.child(idx) {
+        visitor(child.deref());
+      }
+    }
+  }
+
+  fn visit_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    for idx in 0..self.len() {
+      if let Some(mut child) = self.child_mut(idx) {
+        visitor(child.deref_mut());
+      }
+    }
+  }
+
+  /// The child at the index if it is kept rather than built on the fly, see `Widget::get_child`.
+  fn get_child(&self, index: usize) -> Option<&dyn Widget> {
+    match self.child(index)? {
+      Scoped::Ref(child) => Some(child),
+      Scoped::Box(_) => None,
+    }
+  }
+
+  fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+    match self.child_mut(index)? {
+      ScopedMut::Ref(child) => Some(child),
+      ScopedMut::Box(_) => None,
+    }
+  }
 }
 
 impl<W> ChildrenStorage for Vec<W>
@@ -359,3 +673,13 @@ impl Default for Style {
     Self::new()
   }
 }
+
+impl From<&Style> for ContentStyle {
+  fn from(style: &Style) -> Self {
+    ContentStyle {
+      foreground_color: style.fg,
+      background_color: style.bg,
+      attributes: style.attrs,
+    }
+  }
+}
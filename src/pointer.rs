@@ -0,0 +1,204 @@
+//! Mouse input routing, with the higher-level gestures made out of the raw mouse events.
+
+use std::time::Instant;
+
+use crossterm::event::{MouseButton, MouseEvent};
+use euclid::default::{Point2D, Rect, Size2D, Vector2D};
+
+use crate::tree::{self, Frames, Path};
+use crate::widgets::{AnyEvent, EventResult, MouseGesture, Widget, DOUBLE_CLICK_INTERVAL};
+use crate::{Event, MouseEventKind};
+
+/// Button held down, with the widget under the pointer when it was pressed.
+struct Press {
+  button: MouseButton,
+  point: Point2D<usize>,
+  path: Path,
+  rects: Vec<Rect<usize>>,
+  /// Set once the pointer moves, to the depth in path of the widget that took the drag, if any did.
+  drag: Option<Option<usize>>,
+}
+
+impl Press {
+  fn offset(&self, point: &Point2D<usize>) -> Vector2D<isize> {
+    Vector2D::new(
+      point.x as isize - self.point.x as isize,
+      point.y as isize - self.point.y as isize,
+    )
+  }
+}
+
+/// Mouse state across events, for hovering, clicks and drags.
+#[derive(Default)]
+pub(crate) struct Pointer {
+  press: Option<Press>,
+  hover: Path,
+  last_click: Option<(MouseButton, Point2D<usize>, Instant)>,
+}
+
+impl Pointer {
+  /// Deliver a mouse event to the deepest widget under the pointer, bubbling it up the ancestors while unhandled,
+  /// then deliver the gestures it completes. Each widget gets coordinates relative to its frame.
+  /// Double clicks are told apart with `now`, the instant of the event.
+  pub(crate) fn dispatch(
+    &mut self, root: &mut dyn Widget, frames: &Frames, mouse: MouseEvent, size: &Size2D<usize>, now: Instant,
+  ) -> EventResult {
+    let point = Point2D::new(mouse.column as usize, mouse.row as usize);
+    let (path, rects) = tree::hit(root, frames, &point, size);
+    self.hover(root, frames, &path, size);
+
+    let (result, _) = tree::bubble(root, &path, &rects, |frame| {
+      let position = tree::relative(&point, frame);
+      AnyEvent::Input(Event::Mouse(MouseEvent {
+        column: position.x as u16,
+        row: position.y as u16,
+        ..mouse
+      }))
+    });
+    let gesture = match mouse.kind {
+      MouseEventKind::Down(button) => {
+        self.press = Some(Press {
+          button,
+          point,
+          path,
+          rects,
+          drag: None,
+        });
+        EventResult::Unhandled
+      }
+      MouseEventKind::Drag(button) => self.drag(root, button, &point),
+      MouseEventKind::Up(button) => self.release(root, button, &point, &path, &rects, now),
+      MouseEventKind::Moved => EventResult::Unhandled,
+      MouseEventKind::ScrollDown => wheel(root, 1, &point, &path, &rects),
+      MouseEventKind::ScrollUp => wheel(root, -1, &point, &path, &rects),
+    };
+    or(result, gesture)
+  }
+
+  /// Send HoverLeave to the widgets the pointer left, deepest first, and HoverEnter to the ones it entered.
+  fn hover(&mut self, root: &mut dyn Widget, frames: &Frames, path: &[usize], size: &Size2D<usize>) {
+    let common = self.hover.iter().zip(path).take_while(|(old, new)| old == new).count();
+    for depth in (common + 1..=self.hover.len()).rev() {
+      let event = AnyEvent::Mouse(MouseGesture::HoverLeave);
+      tree::send_sized(root, &self.hover[..depth], &event, frames, size);
+    }
+    for depth in common + 1..=path.len() {
+      let event = AnyEvent::Mouse(MouseGesture::HoverEnter);
+      tree::send_sized(root, &path[..depth], &event, frames, size);
+    }
+    self.hover = path.to_vec();
+  }
+
+  fn drag(&mut self, root: &mut dyn Widget, button: MouseButton, point: &Point2D<usize>) -> EventResult {
+    let press = match self.press.as_mut() {
+      Some(press) if press.button == button => press,
+      _ => return EventResult::Unhandled,
+    };
+    if press.drag.is_none() {
+      let origin = press.point;
+      let (result, depth) = tree::bubble(root, &press.path, &press.rects, |frame| {
+        AnyEvent::Mouse(MouseGesture::DragStart {
+          button,
+          origin: tree::relative(&origin, frame),
+        })
+      });
+      press.drag = Some(depth);
+      if depth.is_none() {
+        return result;
+      }
+    }
+    match press.drag {
+      Some(Some(depth)) => {
+        let origin = tree::relative(&press.point, &press.rects[depth]);
+        let event = AnyEvent::Mouse(MouseGesture::DragMove {
+          button,
+          origin,
+          offset: press.offset(point),
+        });
+        send(root, &press.path[..depth], &press.rects[depth], &event)
+      }
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn release(
+    &mut self, root: &mut dyn Widget, button: MouseButton, point: &Point2D<usize>, path: &[usize],
+    rects: &[Rect<usize>], now: Instant,
+  ) -> EventResult {
+    let press = match self.press.take() {
+      Some(press) if press.button == button => press,
+      press => {
+        self.press = press;
+        return EventResult::Unhandled;
+      }
+    };
+    match press.drag {
+      Some(Some(depth)) => {
+        let origin = tree::relative(&press.point, &press.rects[depth]);
+        let event = AnyEvent::Mouse(MouseGesture::DragEnd {
+          button,
+          origin,
+          offset: press.offset(point),
+        });
+        send(root, &press.path[..depth], &press.rects[depth], &event)
+      }
+      Some(None) => EventResult::Unhandled,
+      None if press.path == path => self.click(root, button, point, path, rects, now),
+      None => EventResult::Unhandled,
+    }
+  }
+
+  fn click(
+    &mut self, root: &mut dyn Widget, button: MouseButton, point: &Point2D<usize>, path: &[usize],
+    rects: &[Rect<usize>], now: Instant,
+  ) -> EventResult {
+    let (result, _) = tree::bubble(root, path, rects, |frame| {
+      AnyEvent::Mouse(MouseGesture::Click {
+        button,
+        position: tree::relative(point, frame),
+      })
+    });
+    let double = match self.last_click.take() {
+      Some((last_button, last_point, at)) => {
+        last_button == button && last_point == *point && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+      }
+      None => false,
+    };
+    if !double {
+      self.last_click = Some((button, *point, now));
+      return result;
+    }
+    let (double_result, _) = tree::bubble(root, path, rects, |frame| {
+      AnyEvent::Mouse(MouseGesture::DoubleClick {
+        button,
+        position: tree::relative(point, frame),
+      })
+    });
+    or(result, double_result)
+  }
+}
+
+fn wheel(
+  root: &mut dyn Widget, delta: isize, point: &Point2D<usize>, path: &[usize], rects: &[Rect<usize>],
+) -> EventResult {
+  let (result, _) = tree::bubble(root, path, rects, |frame| {
+    AnyEvent::Mouse(MouseGesture::Wheel {
+      delta,
+      position: tree::relative(point, frame),
+    })
+  });
+  result
+}
+
+/// Send to the widget at path only, with no bubbling.
+fn send(root: &mut dyn Widget, path: &[usize], frame: &Rect<usize>, event: &AnyEvent) -> EventResult {
+  tree::with_widget_mut(root, path, |widget| widget.event(event, &frame.size)).unwrap_or(EventResult::Unhandled)
+}
+
+/// First handled result of the two.
+fn or(first: EventResult, second: EventResult) -> EventResult {
+  match first {
+    EventResult::Unhandled => second,
+    first => first,
+  }
+}
@@ -0,0 +1,559 @@
+//! Recording sessions to asciicast v2 files, along with their input events, and replaying them.
+//!
+//! The cast holds the output written to the terminal, it plays with `asciinema play`.
+//! The input log holds one event per line in the same JSON array form, timed from the same start:
+//! ```text
+//! [0.512300, "key", "a", 0]
+//! [0.914100, "key", "Enter", 0]
+//! [1.200000, "mouse", "down", "left", 3, 4, 0]
+//! [2.000000, "resize", 100, 40]
+//! ```
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent};
+use euclid::default::{Point2D, Size2D};
+
+use crate::backend::Backend;
+use crate::widgets::RenderError;
+use crate::{Event, KeyCode, KeyModifiers, MouseEventKind, WiTui};
+
+/// Records a session given to `WiTui::record`: the output to an asciicast v2 cast,
+/// and the input events to a separate log for `WiTui::replay`.
+/// Both are timed from the first frame.
+pub struct Recorder {
+  cast: Box<dyn Write>,
+  input: Box<dyn Write>,
+  start: Instant,
+}
+
+impl Recorder {
+  pub fn new<C: Write + 'static, I: Write + 'static>(cast: C, input: I) -> Self {
+    Self {
+      cast: Box::new(cast),
+      input: Box::new(input),
+      start: Instant::now(),
+    }
+  }
+
+  /// Record to the given files, created or truncated.
+  pub fn create<C: AsRef<Path>, I: AsRef<Path>>(cast: C, input: I) -> std::io::Result<Self> {
+    let cast = BufWriter::new(File::create(cast)?);
+    let input = BufWriter::new(File::create(input)?);
+    Ok(Self::new(cast, input))
+  }
+
+  fn elapsed(&self) -> f64 {
+    self.start.elapsed().as_secs_f64()
+  }
+
+  // Recording is best effort, failing to write it does not fail the session.
+  // Each line is flushed right away, for the recording to survive a crash.
+
+  fn header(&mut self, size: Size2D<usize>) {
+    self.start = Instant::now();
+    let timestamp = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |time| time.as_secs());
+    writeln!(
+      self.cast,
+      "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}}}",
+      size.width, size.height, timestamp
+    )
+    .ok();
+    self.cast.flush().ok();
+  }
+
+  fn output(&mut self, buf: &[u8]) {
+    let line = format!(
+      "[{:.6}, \"o\", {}]",
+      self.elapsed(),
+      json_string(&String::from_utf8_lossy(buf))
+    );
+    writeln!(self.cast, "{}", line).ok();
+    self.cast.flush().ok();
+  }
+
+  pub(crate) fn input(&mut self, event: &Event) {
+    let elapsed = self.elapsed();
+    if let Event::Resize(cols, rows) = event {
+      let size = format!("{}x{}", cols, rows);
+      writeln!(self.cast, "[{:.6}, \"r\", {}]", elapsed, json_string(&size)).ok();
+      self.cast.flush().ok();
+    }
+    writeln!(self.input, "[{:.6}, {}]", elapsed, encode_event(event)).ok();
+    self.input.flush().ok();
+  }
+}
+
+/// Backend recording what is written to the one it wraps.
+/// Only the required methods are forwarded, the terminal controls being written through `write` to be recorded.
+pub(crate) struct RecordingBackend {
+  backend: Box<dyn Backend>,
+  recorder: Rc<RefCell<Recorder>>,
+}
+
+impl RecordingBackend {
+  pub(crate) fn new(backend: Box<dyn Backend>, recorder: Rc<RefCell<Recorder>>) -> Self {
+    if let Ok(size) = backend.size() {
+      recorder.borrow_mut().header(size);
+    }
+    Self { backend, recorder }
+  }
+}
+
+impl Backend for RecordingBackend {
+  fn size(&self) -> std::io::Result<Size2D<usize>> {
+    self.backend.size()
+  }
+
+  fn enable_raw_mode(&mut self) -> std::io::Result<()> {
+    self.backend.enable_raw_mode()
+  }
+
+  fn disable_raw_mode(&mut self) -> std::io::Result<()> {
+    self.backend.disable_raw_mode()
+  }
+
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>> {
+    self.backend.cursor_position()
+  }
+
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<()> {
+    self.recorder.borrow_mut().output(buf);
+    self.backend.write(buf)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.backend.flush()
+  }
+
+  fn reads_terminal_input(&self) -> bool {
+    self.backend.reads_terminal_input()
+  }
+}
+
+/// Backend of the recorded size keeping the output of a replay.
+struct ReplayBackend {
+  size: Size2D<usize>,
+  output: Rc<RefCell<Vec<String>>>,
+}
+
+impl Backend for ReplayBackend {
+  fn size(&self) -> std::io::Result<Size2D<usize>> {
+    Ok(self.size)
+  }
+
+  fn enable_raw_mode(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn disable_raw_mode(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>> {
+    Ok(Point2D::zero())
+  }
+
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<()> {
+    self.output.borrow_mut().push(String::from_utf8_lossy(buf).into_owned());
+    Ok(())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn reads_terminal_input(&self) -> bool {
+    false
+  }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+  Io(std::io::Error),
+  /// Line of the cast or the input log that could not be read.
+  Malformed(String),
+  Render(RenderError),
+  /// The output diverged from the recording at this write, None being past its end.
+  Mismatch {
+    index: usize,
+    expected: Option<String>,
+    actual: Option<String>,
+  },
+}
+
+impl Display for ReplayError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ReplayError::Io(err) => write!(f, "Replay error: {}", err),
+      ReplayError::Malformed(line) => write!(f, "Replay error: malformed line: {}", line),
+      ReplayError::Render(err) => write!(f, "Replay error: {}", err),
+      ReplayError::Mismatch {
+        index,
+        expected,
+        actual,
+      } => {
+        let show = |output: &Option<String>| output.as_deref().map_or("(nothing)".to_string(), json_string);
+        write!(
+          f,
+          "Replay error: output #{} differs\n  expected: {}\n  actual:   {}",
+          index,
+          show(expected),
+          show(actual)
+        )
+      }
+    }
+  }
+}
+
+impl Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+  fn from(err: std::io::Error) -> Self {
+    ReplayError::Io(err)
+  }
+}
+
+impl From<RenderError> for ReplayError {
+  fn from(err: RenderError) -> Self {
+    ReplayError::Render(err)
+  }
+}
+
+impl WiTui {
+  /// Record the session with the recorder, from the first frame on.
+  pub fn record(mut self, recorder: Recorder) -> Self {
+    self.recorder = Some(Rc::new(RefCell::new(recorder)));
+    self
+  }
+
+  /// Replay a recorded session: its input events are fed back at their recorded times,
+  /// with the ticks and timers run on the recorded clock rather than the real one,
+  /// and the output must match the cast, every frame of it.
+  ///
+  /// The WiTui must be built as it was for the recording and not rendered yet, its backend is replaced.
+  /// What was posted through handles or futures during the recording is not replayed.
+  pub fn replay<C: BufRead, I: BufRead>(mut self, cast: C, input: I) -> Result<(), ReplayError> {
+    let (size, expected, end) = read_cast(cast)?;
+    let events = read_input(input)?;
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let backend = ReplayBackend {
+      size,
+      output: output.clone(),
+    };
+    self.backend = Some(Box::new(backend));
+    self.recorder = None;
+
+    let start = Instant::now();
+    self.timers.set_clock(start);
+    self.next_tick = self.tick_rate.map(|tick_rate| start + tick_rate);
+    self.print()?;
+    let mut quit = false;
+    for (time, event) in events {
+      let now = start + Duration::from_secs_f64(time);
+      self.replay_deadlines(now)?;
+      if std::mem::take(&mut self.quitting) {
+        quit = true;
+        break;
+      }
+      self.timers.set_clock(now);
+      let handled = self.handle_event(event, &mut quit).is_some();
+      if quit {
+        break;
+      }
+      let quitting = std::mem::take(&mut self.quitting);
+      if handled || quitting {
+        self.print()?;
+      }
+      if quitting {
+        quit = true;
+        break;
+      }
+    }
+    // the session may have gone on without input, until quit by a timer for instance
+    if !quit {
+      self.replay_deadlines(start + Duration::from_secs_f64(end))?;
+    }
+    drop(self);
+
+    let actual = output.borrow();
+    for index in 0..expected.len().max(actual.len()) {
+      if expected.get(index) != actual.get(index) {
+        return Err(ReplayError::Mismatch {
+          index,
+          expected: expected.get(index).cloned(),
+          actual: actual.get(index).cloned(),
+        });
+      }
+    }
+    Ok(())
+  }
+
+  /// Run the ticks and the timers due until the given instant, one deadline after the other
+  /// with a frame after each, as the event loop would have.
+  fn replay_deadlines(&mut self, until: Instant) -> Result<(), ReplayError> {
+    while let Some(deadline) = self.next_deadline().filter(|deadline| *deadline <= until) {
+      self.timers.set_clock(deadline);
+      if self.handle_deadlines(deadline) {
+        self.print()?;
+      }
+      if self.quitting {
+        break;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Size, outputs and duration of a cast.
+fn read_cast<C: BufRead>(cast: C) -> Result<(Size2D<usize>, Vec<String>, f64), ReplayError> {
+  let mut lines = cast.lines();
+  let header = lines.next().transpose()?.unwrap_or_default();
+  let field = |name: &str| {
+    let key = format!("\"{}\":", name);
+    let start = header.find(&key)? + key.len();
+    let value = header[start..].trim_start();
+    let end = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    value[..end].parse::<usize>().ok()
+  };
+  let size = match (field("width"), field("height")) {
+    (Some(width), Some(height)) => Size2D::new(width, height),
+    _ => return Err(ReplayError::Malformed(header)),
+  };
+  let mut outputs = Vec::new();
+  let mut end: f64 = 0.0;
+  for line in lines {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    match parse_array(&line).as_deref() {
+      Some([Value::Number(time), Value::Text(code), Value::Text(data)]) => {
+        end = end.max(*time);
+        if code == "o" {
+          outputs.push(data.clone());
+        }
+      }
+      _ => return Err(ReplayError::Malformed(line)),
+    }
+  }
+  Ok((size, outputs, end))
+}
+
+/// Timed events of an input log, the times in seconds.
+fn read_input<I: BufRead>(input: I) -> Result<Vec<(f64, Event)>, ReplayError> {
+  let mut events = Vec::new();
+  for line in input.lines() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let event = parse_array(&line).and_then(|values| match values.split_first() {
+      Some((Value::Number(time), event)) => decode_event(event).map(|event| (*time, event)),
+      _ => None,
+    });
+    match event {
+      Some(event) => events.push(event),
+      None => return Err(ReplayError::Malformed(line)),
+    }
+  }
+  Ok(events)
+}
+
+fn encode_event(event: &Event) -> String {
+  match event {
+    Event::Key(key) => format!(
+      "\"key\", {}, {}",
+      json_string(&key_code_name(key.code)),
+      key.modifiers.bits()
+    ),
+    Event::Mouse(mouse) => {
+      let (kind, button) = match mouse.kind {
+        MouseEventKind::Down(button) => ("down", Some(button)),
+        MouseEventKind::Up(button) => ("up", Some(button)),
+        MouseEventKind::Drag(button) => ("drag", Some(button)),
+        MouseEventKind::Moved => ("moved", None),
+        MouseEventKind::ScrollDown => ("scroll_down", None),
+        MouseEventKind::ScrollUp => ("scroll_up", None),
+      };
+      let button = match button {
+        Some(MouseButton::Left) => "left",
+        Some(MouseButton::Right) => "right",
+        Some(MouseButton::Middle) => "middle",
+        None => "",
+      };
+      format!(
+        "\"mouse\", \"{}\", \"{}\", {}, {}, {}",
+        kind,
+        button,
+        mouse.column,
+        mouse.row,
+        mouse.modifiers.bits()
+      )
+    }
+    Event::Resize(cols, rows) => format!("\"resize\", {}, {}", cols, rows),
+  }
+}
+
+fn decode_event(values: &[Value]) -> Option<Event> {
+  use Value::{Number, Text};
+  let modifiers = |bits: &f64| KeyModifiers::from_bits_truncate(*bits as u8);
+  match values {
+    [Text(kind), Text(code), Number(bits)] if kind == "key" => {
+      Some(Event::Key(KeyEvent::new(key_code(code)?, modifiers(bits))))
+    }
+    [Text(kind), Text(mouse_kind), Text(button), Number(column), Number(row), Number(bits)] if kind == "mouse" => {
+      let button = match button.as_str() {
+        "left" => Some(MouseButton::Left),
+        "right" => Some(MouseButton::Right),
+        "middle" => Some(MouseButton::Middle),
+        _ => None,
+      };
+      let kind = match (mouse_kind.as_str(), button) {
+        ("down", Some(button)) => MouseEventKind::Down(button),
+        ("up", Some(button)) => MouseEventKind::Up(button),
+        ("drag", Some(button)) => MouseEventKind::Drag(button),
+        ("moved", _) => MouseEventKind::Moved,
+        ("scroll_down", _) => MouseEventKind::ScrollDown,
+        ("scroll_up", _) => MouseEventKind::ScrollUp,
+        _ => return None,
+      };
+      Some(Event::Mouse(MouseEvent {
+        kind,
+        column: *column as u16,
+        row: *row as u16,
+        modifiers: modifiers(bits),
+      }))
+    }
+    [Text(kind), Number(cols), Number(rows)] if kind == "resize" => Some(Event::Resize(*cols as u16, *rows as u16)),
+    _ => None,
+  }
+}
+
+/// Name of the key, the character itself for the character keys.
+fn key_code_name(code: KeyCode) -> String {
+  match code {
+    KeyCode::Char(char) => char.to_string(),
+    KeyCode::F(number) => format!("F{}", number),
+    code => format!("{:?}", code),
+  }
+}
+
+fn key_code(name: &str) -> Option<KeyCode> {
+  let mut chars = name.chars();
+  if let (Some(char), None) = (chars.next(), chars.next()) {
+    return Some(KeyCode::Char(char));
+  }
+  let code = match name {
+    "Backspace" => KeyCode::Backspace,
+    "Enter" => KeyCode::Enter,
+    "Left" => KeyCode::Left,
+    "Right" => KeyCode::Right,
+    "Up" => KeyCode::Up,
+    "Down" => KeyCode::Down,
+    "Home" => KeyCode::Home,
+    "End" => KeyCode::End,
+    "PageUp" => KeyCode::PageUp,
+    "PageDown" => KeyCode::PageDown,
+    "Tab" => KeyCode::Tab,
+    "BackTab" => KeyCode::BackTab,
+    "Delete" => KeyCode::Delete,
+    "Insert" => KeyCode::Insert,
+    "Null" => KeyCode::Null,
+    "Esc" => KeyCode::Esc,
+    name => KeyCode::F(name.strip_prefix('F')?.parse().ok()?),
+  };
+  Some(code)
+}
+
+/// Item of the flat JSON arrays the cast and the input log are made of.
+#[derive(Debug, PartialEq)]
+enum Value {
+  Number(f64),
+  Text(String),
+}
+
+fn json_string(text: &str) -> String {
+  let mut json = String::with_capacity(text.len() + 2);
+  json.push('"');
+  for char in text.chars() {
+    match char {
+      '"' => json.push_str("\\\""),
+      '\\' => json.push_str("\\\\"),
+      '\n' => json.push_str("\\n"),
+      '\r' => json.push_str("\\r"),
+      '\t' => json.push_str("\\t"),
+      char if char.is_control() => json.push_str(&format!("\\u{:04x}", char as u32)),
+      char => json.push(char),
+    }
+  }
+  json.push('"');
+  json
+}
+
+/// Parse a JSON array of numbers and strings, None if it is anything else.
+fn parse_array(line: &str) -> Option<Vec<Value>> {
+  let mut chars = line.trim().chars().peekable();
+  let mut values = Vec::new();
+  if chars.next()? != '[' {
+    return None;
+  }
+  loop {
+    while chars.next_if(|char| char.is_whitespace()).is_some() {}
+    match chars.next()? {
+      ']' if values.is_empty() => break,
+      '"' => {
+        let mut text = String::new();
+        loop {
+          match chars.next()? {
+            '"' => break,
+            '\\' => match chars.next()? {
+              'n' => text.push('\n'),
+              'r' => text.push('\r'),
+              't' => text.push('\t'),
+              'b' => text.push('\u{8}'),
+              'f' => text.push('\u{c}'),
+              'u' => {
+                let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+                let mut code = u32::from_str_radix(&hex, 16).ok()?;
+                // characters out of the basic plane come as surrogate pairs
+                if (0xd800..0xdc00).contains(&code) {
+                  let low: String = (0..6).filter_map(|_| chars.next()).collect();
+                  let low = u32::from_str_radix(low.strip_prefix("\\u")?, 16).ok()?;
+                  code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                }
+                text.push(char::from_u32(code)?);
+              }
+              char => text.push(char),
+            },
+            char => text.push(char),
+          }
+        }
+        values.push(Value::Text(text));
+      }
+      char => {
+        let mut number = char.to_string();
+        while let Some(char) = chars.next_if(|char| !matches!(char, ',' | ']') && !char.is_whitespace()) {
+          number.push(char);
+        }
+        values.push(Value::Number(number.parse().ok()?));
+      }
+    }
+    while chars.next_if(|char| char.is_whitespace()).is_some() {}
+    match chars.next()? {
+      ',' => continue,
+      ']' => break,
+      _ => return None,
+    }
+  }
+  Some(values)
+}
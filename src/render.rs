@@ -1,19 +1,17 @@
 use std::borrow::{Borrow, BorrowMut};
 use std::cell::{Cell, Ref, RefCell, RefMut};
-use std::io::Write;
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
 use crate::log::trace;
-use crossterm::style::{Attributes, Color};
-use crossterm::terminal::{Clear, ClearType, DisableLineWrap, EnableLineWrap, ScrollDown, ScrollUp};
-use crossterm::{cursor, execute, terminal};
+use crossterm::style::{Attributes, Color, ContentStyle};
 use euclid::default::{Box2D, Point2D, Rect, Size2D};
 
+use crate::backend::{Backend, CrosstermBackend, HeadlessBackend};
 use crate::canvas::Canvas;
-use crate::util::{Immut, Immutable};
-use crate::widgets::{RenderResult, Widget};
-use crossterm::event;
+use crate::tree::{Frames, Path};
+use crate::util::{display_width, truncate, Immut, Immutable};
+use crate::widgets::{RenderError, RenderResult, Widget};
 
 // TODO: Check https://docs.rs/sdl2/0.34.5/sdl2/render/
 
@@ -21,8 +19,6 @@ use crossterm::event;
 //  PUSH settings on Context creation
 //  POP settings on Context drop
 
-// TODO: push/pop context with widget ID, for event hierarchy tracking later
-
 pub struct Renderer {
   size: Size2D<usize>,
   reset_pos: Point2D<usize>,
@@ -31,50 +27,103 @@ pub struct Renderer {
   frame_cursor: Point2D<usize>,
   nl_counter: usize,
   alternate: bool,
+  /// Drawing to a viewport of `size` lines starting at the terminal row `reset_pos.y`.
+  inline: bool,
+  /// Lines to be printed above the inline viewport on the next flush.
+  pending_lines: Vec<String>,
+  style: ContentStyle,
   canvas: Canvas,
+  frames: Frames,
+  last_frames: Frames,
+  backend: Box<dyn Backend>,
 }
 
 impl Renderer {
-  pub(crate) fn new(alternate: bool) -> Self {
-    let mut stdout = std::io::stdout();
+  /// Fails without touching the terminal when its size can't be queried or raw mode can't be entered,
+  /// as when there is no tty.
+  pub(crate) fn new(mut backend: Box<dyn Backend>, alternate: bool) -> std::io::Result<Self> {
+    let size = backend.size()?;
+    backend.enable_raw_mode()?;
     if alternate {
-      execute!(stdout, terminal::EnterAlternateScreen);
-      execute!(stdout, terminal::Clear(ClearType::All));
-      execute!(stdout, cursor::MoveTo(0, 0));
-      execute!(stdout, cursor::Hide);
-      execute!(stdout, terminal::DisableLineWrap);
-      execute!(stdout, event::EnableMouseCapture);
+      backend.enter_alternate_screen().ok();
+      backend.clear().ok();
+      backend.move_cursor(Point2D::new(0, 0)).ok();
+      backend.hide_cursor().ok();
+      backend.disable_line_wrap().ok();
+      backend.enable_mouse_capture().ok();
     }
-    terminal::enable_raw_mode().unwrap();
-    let (cols, rows) = terminal::size().unwrap();
-    let (pos_c, pos_r) = if alternate { (0, 0) } else { cursor::position().unwrap_or((0, 0)) };
-    trace!("INITIAL SIZE: ({},{})", rows, cols);
-    let mut stdout = std::io::stdout();
+    let reset_pos = if alternate { Point2D::zero() } else { backend.cursor_position().unwrap_or(Point2D::zero()) };
+    trace!("INITIAL SIZE: ({},{})", size.height, size.width);
+    Ok(Self::with_viewport(backend, size, reset_pos, alternate, false))
+  }
+
+  /// Renderer of a viewport of the given height, from the line below the prompt on,
+  /// the terminal being scrolled up for it to fit.
+  pub(crate) fn inline(mut backend: Box<dyn Backend>, height: usize) -> std::io::Result<Self> {
+    let terminal = backend.size()?;
+    backend.enable_raw_mode()?;
+    backend.hide_cursor().ok();
+    let height = height.min(terminal.height).max(1);
+    let cursor = backend.cursor_position().unwrap_or(Point2D::zero());
+    // a prompt not ended with a newline keeps its line
+    let top = if cursor.x > 0 { cursor.y + 1 } else { cursor.y };
+    let overflow = (top + height).saturating_sub(terminal.height);
+    if overflow > 0 {
+      backend.scroll_up(overflow).ok();
+    }
+    let size = Size2D::new(terminal.width, height);
+    let reset_pos = Point2D::new(0, top - overflow);
+    trace!("INLINE VIEWPORT: ({},{}) at row {}", size.height, size.width, reset_pos.y);
+    Ok(Self::with_viewport(backend, size, reset_pos, false, true))
+  }
+
+  /// Renderer of a virtual canvas, nothing is written anywhere.
+  fn headless(size: Size2D<usize>) -> Self {
+    Self::with_viewport(Box::new(HeadlessBackend::new(size)), size, Point2D::zero(), true, false)
+  }
+
+  fn with_viewport(
+    backend: Box<dyn Backend>, size: Size2D<usize>, reset_pos: Point2D<usize>, alternate: bool, inline: bool,
+  ) -> Self {
     let mut this = Self {
-      size: Size2D::new(cols as usize, rows as usize),
-      reset_pos: Point2D::new(pos_c as usize, pos_r as usize),
-      base_frame: Rect::from_size(Size2D::new(cols as usize, rows as usize)),
-      frame: Rect::from_size(Size2D::new(cols as usize, rows as usize)),
+      size,
+      reset_pos,
+      base_frame: Rect::from_size(size),
+      frame: Rect::from_size(size),
       frame_cursor: Point2D::new(0, 0),
       nl_counter: 0,
       alternate,
-      canvas: Canvas::new(Size2D::new(cols as usize, rows as usize)),
+      inline,
+      pending_lines: Vec::new(),
+      style: ContentStyle::default(),
+      canvas: Canvas::new(size),
+      frames: Frames::default(),
+      last_frames: Frames::default(),
+      backend,
     };
-    this.set_frame(Rect::from_size(Size2D::new(cols as usize, rows as usize)));
+    this.set_frame(Rect::from_size(size));
     this
   }
 
   fn resize(&mut self, cols: usize, rows: usize) {
     // return; // TODO: remove
     // assert!(self.alternate);
+    let terminal_rows = rows;
+    let rows = if self.inline { self.size.height.min(rows).max(1) } else { rows };
     self.size.width = cols;
     self.size.height = rows;
     self.nl_counter = 0;
     self.base_frame = Rect::from_size(Size2D::new(cols as usize, rows as usize));
     self.set_frame(Rect::from_size(Size2D::new(cols as usize, rows as usize)));
     self.canvas.resize(Size2D::new(cols as usize, rows as usize));
-    let mut stdout = std::io::stdout();
-    // execute!(stdout, terminal::Clear(ClearType::All));
+    // self.backend.clear();
+    if self.inline {
+      // the terminal may have reflowed the viewport, so it is drawn all over again
+      self.reset_pos.y = self.reset_pos.y.min(terminal_rows.saturating_sub(rows));
+      self.backend.move_cursor(self.reset_pos).ok();
+      self.backend.clear_below().ok();
+      self.canvas.force_render_once = true;
+    }
   }
 
   pub(crate) fn force_render_once(&mut self) {
@@ -87,37 +136,86 @@ impl Renderer {
     if space == 0 {
       return;
     }
-    if buf.chars().count() > space {
-      let buf = buf.chars().take(space).collect::<String>();
-      // print!("{}", buf);
-      self.canvas.write(&self.frame_cursor, buf.as_str());
-      self.frame_cursor.x += space;
-    } else {
-      // print!("{}", buf);
-      self.canvas.write(&self.frame_cursor, buf);
-      self.frame_cursor.x += buf.len();
-    }
+    let (buf, _) = truncate(buf, space);
+    // print!("{}", buf);
+    self.frame_cursor.x += self.canvas.write(&self.frame_cursor, buf, &self.style);
     // std::io::stdout().flush();
   }
 
   pub fn flush(&mut self) {
-    self.canvas.render();
+    self.print_pending_lines();
+    let top = if self.inline { Some(self.reset_pos.y) } else { None };
+    self.canvas.render(self.backend.as_mut(), top);
+    self.last_frames = std::mem::take(&mut self.frames);
+    // the next frame is drawn from the top again
+    self.frame = self.base_frame;
+    self.frame_cursor = self.frame.min();
+    self.style = ContentStyle::default();
+  }
+
+  pub(crate) fn reads_terminal_input(&self) -> bool {
+    self.backend.reads_terminal_input()
+  }
+
+  /// Print a line above the inline viewport on the next flush, scrolling up the terminal content above it.
+  /// Ignored outside of the inline viewport, where there is no room above.
+  pub fn println(&mut self, line: &str) {
+    if self.inline {
+      self.pending_lines.extend(line.lines().map(str::to_string));
+    }
   }
 
+  /// Print the pending lines where the viewport is, then move the viewport down below them.
+  fn print_pending_lines(&mut self) {
+    if self.pending_lines.is_empty() {
+      return;
+    }
+    let terminal_rows = self.backend.size().map_or(self.reset_pos.y + self.size.height, |size| size.height);
+    self.backend.move_cursor(self.reset_pos).ok();
+    self.backend.clear_below().ok();
+    for line in std::mem::take(&mut self.pending_lines) {
+      // long lines wrap around
+      let rows = display_width(&line).div_ceil(self.size.width).max(1);
+      let overflow = (self.reset_pos.y + rows + self.size.height).saturating_sub(terminal_rows);
+      let overflow = overflow.min(self.reset_pos.y);
+      if overflow > 0 {
+        self.backend.scroll_up(overflow).ok();
+        self.reset_pos.y -= overflow;
+      }
+      self.backend.move_cursor(self.reset_pos).ok();
+      self.backend.write(line.as_bytes()).ok();
+      self.reset_pos.y = (self.reset_pos.y + rows).min(terminal_rows.saturating_sub(self.size.height));
+    }
+    self.canvas.force_render_once = true;
+  }
+
+  /// Style given to the cells written from now on, inherited from the rendering widget's ancestors.
+  pub fn style(&self) -> &ContentStyle {
+    &self.style
+  }
+
+  /// Paint the background of the current frame, the cells written over it afterwards keep it.
   pub fn set_background(&mut self, color: &Color) {
     self.canvas.fill_background(&self.frame, color);
   }
 
+  /// Paint the foreground of the current frame. The cells the rendering widget and its children write
+  /// afterwards take it too, until the widget is rendered.
   pub fn set_foreground(&mut self, color: &Color) {
     self.canvas.fill_foreground(&self.frame, color);
+    self.style.foreground_color = Some(*color);
   }
 
+  /// Replace the attributes of the current frame, scoped to the rendering widget as with `set_foreground`.
   pub fn set_attributes(&mut self, attributes: Attributes) {
     self.canvas.overwrite_attributes(&self.frame, attributes);
+    self.style.attributes = attributes;
   }
 
+  /// Add attributes to the current frame, scoped to the rendering widget as with `set_foreground`.
   pub fn add_attributes(&mut self, attributes: Attributes) {
     self.canvas.merge_attributes(&self.frame, attributes);
+    self.style.attributes = self.style.attributes | attributes;
   }
 
   pub fn next_line(&mut self) {
@@ -126,7 +224,6 @@ impl Renderer {
     }
     self.frame_cursor.x = self.frame.min_x();
     self.frame_cursor.y += 1;
-    let mut stdout = std::io::stdout();
     // execute!(
     //   stdout,
     //   cursor::MoveTo(
@@ -136,11 +233,9 @@ impl Renderer {
     //   // cursor::MoveToNextLine(1),
     //   // cursor::MoveToColumn(self.frame.min_x() as u16)
     // );
-    if !self.alternate {
-      if self.reset_pos.y + self.frame_cursor.y >= self.size.height {
-        self.reset_pos.y = self.reset_pos.y.checked_sub(1).unwrap_or(0);
-        execute!(stdout, crossterm::terminal::ScrollUp(1),);
-      }
+    if !self.alternate && !self.inline && self.reset_pos.y + self.frame_cursor.y >= self.size.height {
+      self.reset_pos.y = self.reset_pos.y.checked_sub(1).unwrap_or(0);
+      self.backend.scroll_up(1).ok();
     }
     if self.frame_cursor.y >= self.nl_counter {
       self.nl_counter += 1;
@@ -162,16 +257,15 @@ impl Renderer {
     // .unwrap();
     self.frame = frame;
     self.frame_cursor = frame.min();
-    let mut stdout = std::io::stdout();
     // execute!(
     //   stdout,
     //   cursor::MoveTo(frame.min_x() as u16, (frame.min_y() + self.reset_pos.y) as u16)
     // );
 
-    if self.reset_pos.y + self.frame_cursor.y + 1 >= self.size.height {
+    if !self.inline && self.reset_pos.y + self.frame_cursor.y + 1 >= self.size.height {
       let diff = self.reset_pos.y + self.frame_cursor.y + 1 - self.size.height;
       self.reset_pos.y -= diff;
-      execute!(stdout, crossterm::terminal::ScrollUp(diff as u16),);
+      self.backend.scroll_up(diff).ok();
     }
     if self.frame_cursor.y >= self.nl_counter {
       self.nl_counter = self.frame_cursor.y;
@@ -186,7 +280,6 @@ impl Renderer {
       return None;
     }
     self.frame_cursor = Point2D::new(x as usize, y as usize);
-    let mut stdout = std::io::stdout();
     // execute!(stdout, cursor::MoveTo(x, y + self.reset_pos.y as u16));
     Some(())
   }
@@ -198,44 +291,126 @@ impl Renderer {
 
 impl Drop for Renderer {
   fn drop(&mut self) {
-    let mut stdout = std::io::stdout();
-
     if self.alternate {
       // std::thread::sleep(std::time::Duration::from_secs(20));
-      execute!(stdout, terminal::EnableLineWrap);
-      execute!(stdout, cursor::Show);
-      execute!(stdout, event::DisableMouseCapture);
-      execute!(stdout, terminal::LeaveAlternateScreen);
+      self.backend.enable_line_wrap().ok();
+      self.backend.show_cursor().ok();
+      self.backend.disable_mouse_capture().ok();
+      self.backend.leave_alternate_screen().ok();
+    } else if self.inline {
+      // the lines printed since the last frame still go above it
+      if !self.pending_lines.is_empty() {
+        self.canvas.redraw();
+        self.print_pending_lines();
+        self.canvas.render(self.backend.as_mut(), Some(self.reset_pos.y));
+      }
+      let bottom = self.reset_pos.y + self.size.height - 1;
+      self.backend.move_cursor(Point2D::new(0, bottom)).ok();
+      self.backend.show_cursor().ok();
     } else {
-      execute!(stdout, cursor::MoveTo(0, (self.reset_pos.y + self.nl_counter) as u16),);
+      self
+        .backend
+        .move_cursor(Point2D::new(0, self.reset_pos.y + self.nl_counter))
+        .ok();
     }
 
-    terminal::disable_raw_mode().unwrap();
+    self.backend.disable_raw_mode().ok();
 
     if !self.alternate {
-      println!();
+      self.backend.write(b"\n").ok();
     }
-    stdout.flush();
+    self.backend.flush().ok();
   }
 }
 
+/// Where the widget of a RenderCtx is in the tree, for the children it renders to be recorded by their path.
+#[derive(Clone)]
+struct Place {
+  path: Path,
+  /// Address and size of each child the widget visits, telling which of them is rendered.
+  children: Rc<Vec<(usize, usize)>>,
+  /// Children rendered so far, zero-sized siblings sharing their address are told apart by the render order.
+  rendered: Rc<RefCell<Vec<bool>>>,
+}
+
+impl Place {
+  fn of<W: Widget + ?Sized>(widget: &W, path: Path) -> Self {
+    let mut children = Vec::new();
+    widget.visit_children(&mut |child| children.push(identity(child)));
+    let rendered = vec![false; children.len()];
+    Self {
+      path,
+      children: Rc::new(children),
+      rendered: Rc::new(RefCell::new(rendered)),
+    }
+  }
+
+  /// Place of the child being rendered, None if the widget renders something else than one of its children.
+  fn child(&self, child: &dyn Widget) -> Option<Self> {
+    let identity = identity(child);
+    let mut same = (0..self.children.len()).filter(|idx| self.children[*idx] == identity);
+    let first = same.next()?;
+    let mut rendered = self.rendered.deref().borrow_mut();
+    let idx = if rendered[first] { same.find(|idx| !rendered[*idx]).unwrap_or(first) } else { first };
+    rendered[idx] = true;
+    let mut path = self.path.clone();
+    path.push(idx);
+    Some(Self::of(child, path))
+  }
+}
+
+/// Address and size of a widget, the size telling apart a widget from its first field.
+fn identity(widget: &dyn Widget) -> (usize, usize) {
+  (widget as *const dyn Widget as *const () as usize, std::mem::size_of_val(widget))
+}
+
 pub struct RenderCtx {
   // widget constraints box
   renderer: Rc<RefCell<Renderer>>,
   frame: Rect<usize>,
   depth: usize,
   actual_frame: Rect<usize>,
+  style: ContentStyle,
+  /// None outside of `render_root`, the widgets are then not recorded.
+  place: Option<Place>,
   // parent: Option<Box<RenderCtx>>,
 }
 
 impl RenderCtx {
-  pub fn new(alternate: bool) -> Self {
+  /// Context drawing to the terminal on stdout, fails when stdout is not a terminal.
+  pub fn new(alternate: bool) -> std::io::Result<Self> {
+    Self::with_backend(CrosstermBackend::stdout(), alternate)
+  }
+
+  pub fn with_backend<B: Backend + 'static>(backend: B, alternate: bool) -> std::io::Result<Self> {
+    Self::with_boxed_backend(Box::new(backend), alternate)
+  }
+
+  /// Off-screen context on a virtual canvas of the given size, it does not take over the terminal.
+  /// After rendering a widget, `renderer().flush()` commits the frame to be inspected through `canvas()`.
+  pub fn headless(size: Size2D<usize>) -> Self {
+    Self::with_renderer(Renderer::headless(size))
+  }
+
+  pub fn with_boxed_backend(backend: Box<dyn Backend>, alternate: bool) -> std::io::Result<Self> {
+    Ok(Self::with_renderer(Renderer::new(backend, alternate)?))
+  }
+
+  /// Context drawing to an inline viewport of the given height below the cursor,
+  /// each frame being drawn over the previous one in place.
+  pub fn inline(backend: Box<dyn Backend>, height: usize) -> std::io::Result<Self> {
+    Ok(Self::with_renderer(Renderer::inline(backend, height)?))
+  }
+
+  fn with_renderer(renderer: Renderer) -> Self {
     let mut this = Self {
-      renderer: Rc::new(RefCell::new(Renderer::new(alternate))),
+      renderer: Rc::new(RefCell::new(renderer)),
       frame: Default::default(),
       depth: 0,
       // parent: None,
       actual_frame: Default::default(),
+      style: ContentStyle::default(),
+      place: None,
     };
     let frame = this.renderer().frame.clone();
     this.frame = frame;
@@ -247,6 +422,16 @@ impl RenderCtx {
     self.renderer.deref().borrow_mut()
   }
 
+  /// Canvas holding the last flushed frame.
+  pub fn canvas(&self) -> Ref<'_, Canvas> {
+    Ref::map(self.renderer.deref().borrow(), |renderer| &renderer.canvas)
+  }
+
+  /// Frames the widgets were rendered into in the last flushed frame, for routing events.
+  pub(crate) fn frames(&self) -> Ref<'_, Frames> {
+    Ref::map(self.renderer.deref().borrow(), |renderer| &renderer.last_frames)
+  }
+
   pub fn get_frame(&self) -> &Rect<usize> {
     &self.frame
   }
@@ -256,18 +441,62 @@ impl RenderCtx {
     self.renderer().set_frame(frame);
   }
 
+  /// Style inherited by this widget from its ancestors.
+  pub fn get_style(&self) -> &ContentStyle {
+    &self.style
+  }
+
+  /// Render the root of a widget tree. Unlike calling the root's `render` with this context,
+  /// the widgets are recorded by their path in the tree, for the events to reach them.
+  pub fn render_root<W: Widget + ?Sized>(&self, root: &W) -> RenderResult {
+    let ctx = Self {
+      renderer: self.renderer.clone(),
+      frame: self.frame,
+      depth: self.depth,
+      actual_frame: self.actual_frame,
+      style: self.style,
+      place: Some(Place::of(root, Path::new())),
+    };
+    self.renderer().frames.record(Path::new(), self.frame, self.actual_frame);
+    root.render(&ctx)
+  }
+
   #[inline]
   pub fn render_child_widget<W: Widget>(&self, frame: Rect<usize>, child: &W) -> RenderResult {
     self.render_child_dyn_widget(frame, child as &dyn Widget)
   }
 
   pub fn render_child_dyn_widget(&self, frame: Rect<usize>, child: &dyn Widget) -> RenderResult {
+    self.render_child_dyn_widget_styled(frame, &ContentStyle::default(), child)
+  }
+
+  #[inline]
+  pub fn render_child_widget_styled<W: Widget>(
+    &self, frame: Rect<usize>, style: &ContentStyle, child: &W,
+  ) -> RenderResult {
+    self.render_child_dyn_widget_styled(frame, style, child as &dyn Widget)
+  }
+
+  /// Render a child with a style composed on top of the inherited one.
+  /// Colors override the inherited ones and attributes add up, a background color is also painted
+  /// over the child frame. The inherited style is restored once the child is rendered.
+  pub fn render_child_dyn_widget_styled(
+    &self, frame: Rect<usize>, style: &ContentStyle, child: &dyn Widget,
+  ) -> RenderResult {
     let input_frame = frame.clone();
+    // the renderer's style is this widget's, along with what it set on the renderer
+    let inherited = self.renderer().style;
     let mut child_ctx = Self {
       renderer: self.renderer.clone(),
       frame,
       depth: self.depth + 1,
       actual_frame: Default::default(),
+      style: ContentStyle {
+        foreground_color: style.foreground_color.or(inherited.foreground_color),
+        background_color: style.background_color.or(inherited.background_color),
+        attributes: inherited.attributes | style.attributes,
+      },
+      place: None,
     };
     let actual_child_frame = if self.frame.intersects(&child_ctx.frame) {
       self
@@ -287,12 +516,84 @@ impl RenderCtx {
       &input_frame,
       actual_child_frame
     );
+    // what is not one of the children, like a temporary, is rendered in the widget's place
+    child_ctx.place = self.place.as_ref().map(|place| match place.child(child) {
+      Some(child_place) => {
+        let path = child_place.path.clone();
+        self.renderer().frames.record(path, input_frame, actual_child_frame);
+        child_place
+      }
+      None => place.clone(),
+    });
     self.renderer().set_frame(actual_child_frame);
+    self.renderer().style = child_ctx.style;
+    if let Some(bg) = style.background_color.as_ref() {
+      self.renderer().set_background(bg);
+    }
     let result = child.render(&child_ctx);
     self.renderer().set_frame(self.frame.clone());
+    self.renderer().style = inherited;
     result
   }
 
+  #[inline]
+  pub fn render_child_widget_scrolled<W: Widget>(
+    &self, size: Size2D<usize>, offset: Point2D<usize>, child: &W,
+  ) -> RenderResult {
+    self.render_child_dyn_widget_scrolled(size, offset, child as &dyn Widget)
+  }
+
+  /// Render a child of the given size on an off-screen canvas, then draw the part of it from the offset on
+  /// over this widget's frame. The child's widgets are recorded where they show, for the mouse to reach them.
+  pub fn render_child_dyn_widget_scrolled(
+    &self, size: Size2D<usize>, offset: Point2D<usize>, child: &dyn Widget,
+  ) -> RenderResult {
+    self.render_scrolled(size, offset, child).map(|_| ())
+  }
+
+  /// Same as `render_child_dyn_widget_scrolled`, returning the frames the child's widgets were rendered into
+  /// on the off-screen canvas, by their path below this widget.
+  pub(crate) fn render_scrolled(
+    &self, size: Size2D<usize>, offset: Point2D<usize>, child: &dyn Widget,
+  ) -> Result<Frames, RenderError> {
+    if size.is_empty() || self.actual_frame.is_empty() {
+      return Ok(Frames::default());
+    }
+    let mut offscreen = Self::headless(size);
+    offscreen.style = self.renderer().style;
+    offscreen.renderer().style = offscreen.style;
+    offscreen.place = self.place.clone();
+    offscreen.render_child_dyn_widget(Rect::from_size(size), child)?;
+
+    let mut source = offscreen.renderer();
+    let mut renderer = self.renderer();
+    let frames = std::mem::take(&mut source.frames);
+    let content = match self.place.as_ref() {
+      Some(place) => frames.below(&place.path),
+      None => Frames::default(),
+    };
+    renderer
+      .frames
+      .append(frames, offset, self.frame.origin, &self.actual_frame);
+    // part of the child showing through the visible part of the frame
+    let shown = Rect::new(offset + (self.actual_frame.origin - self.frame.origin), self.actual_frame.size);
+    if let Some(shown) = shown.intersection(&Rect::from_size(size)) {
+      let origin = self.actual_frame.origin;
+      renderer.set_frame(self.actual_frame);
+      for row in 0..shown.height() {
+        if row > 0 {
+          renderer.next_line();
+        }
+        let line = Rect::new(Point2D::new(shown.min_x(), shown.min_y() + row), Size2D::new(shown.width(), 1));
+        renderer
+          .canvas
+          .blit(&source.canvas, &line, &Point2D::new(origin.x, origin.y + row));
+      }
+      renderer.set_frame(self.actual_frame);
+    }
+    Ok(content)
+  }
+
   pub fn resize(&mut self, cols: usize, rows: usize) {
     self.renderer.deref().borrow_mut().resize(cols, rows);
     let frame = self.renderer().frame.clone();
@@ -0,0 +1,121 @@
+//! Async event loop, behind the `tokio` feature.
+
+use std::any::Any;
+use std::future::Future;
+use std::time::Instant;
+
+use crossterm::event::EventStream;
+use futures::stream::{LocalBoxStream, SelectAll};
+use futures::{FutureExt, Stream, StreamExt};
+
+use crate::handle::Message;
+use crate::widgets::{RenderError, RenderResult};
+use crate::{Event, WiTui};
+
+/// App-supplied streams, polled along with the input.
+pub(crate) type Streams = SelectAll<LocalBoxStream<'static, Message>>;
+
+/// What woke the event loop up.
+enum Wake {
+  Input(Option<crossterm::Result<Event>>),
+  Message(Message),
+  Posted,
+  Timeout,
+}
+
+impl WiTui {
+  /// Send each item of the stream to every widget of the tree as `AnyEvent::Custom`, while `run` runs.
+  pub fn stream<S>(&mut self, stream: S)
+  where
+    S: Stream + 'static,
+    S::Item: Any + Send,
+  {
+    let stream = stream.map(|item| Message::Custom(Box::new(item)));
+    self.streams.push(stream.boxed_local());
+  }
+
+  /// Send the output of the future to every widget of the tree as `AnyEvent::Custom`, once `run` got it.
+  pub fn future<F>(&mut self, future: F)
+  where
+    F: Future + 'static,
+    F::Output: Any + Send,
+  {
+    self.stream(future.into_stream());
+  }
+
+  /// Run the closure with the WiTui once the future is ready, to mutate the widget tree.
+  pub fn future_update<F, U>(&mut self, future: F)
+  where
+    F: Future<Output = U> + 'static,
+    U: FnOnce(&mut WiTui) + Send + 'static,
+  {
+    let stream = future.map(|update| Message::Update(Box::new(update))).into_stream();
+    self.streams.push(stream.boxed_local());
+  }
+
+  /// Async counterpart of `run_loop`, with the input read from crossterm's `EventStream`.
+  /// Must be awaited within a tokio runtime, the WiTui being not Send, on the thread it was built.
+  pub async fn run(&mut self) -> RenderResult {
+    self.run_with_input(EventStream::new()).await
+  }
+
+  /// Same as `run` with the input read from the given stream, the loop ends along with the stream.
+  /// An error read from the stream ends the loop as `RenderError::Io`, as does the terminal failing to set up.
+  /// Render errors of the frames are not reported, as with `run_loop`.
+  pub async fn run_with_input<S>(&mut self, mut input: S) -> RenderResult
+  where
+    S: Stream<Item = crossterm::Result<Event>> + Unpin,
+  {
+    self.render_ctx().map_err(RenderError::Io)?;
+    loop {
+      // a frame that fails to render, on a terminal too small for it for instance, is skipped
+      self.print().ok();
+      if !self.interactive() || std::mem::take(&mut self.quitting) {
+        break;
+      }
+      let mut quit = false;
+      self.event_stream(&mut input, &mut quit).await.map_err(RenderError::Io)?;
+      if quit {
+        break;
+      }
+    }
+    Ok(())
+  }
+
+  /// Await the input, the app streams, the ticks, the timers and the handles, returns once a new frame is needed.
+  async fn event_stream<S>(&mut self, input: &mut S, quit: &mut bool) -> std::io::Result<()>
+  where
+    S: Stream<Item = crossterm::Result<Event>> + Unpin,
+  {
+    loop {
+      let timeout = self.poll_timeout();
+      let streams = &mut self.streams;
+      let posted = self.mailbox.posted.clone();
+      let wake = tokio::select! {
+        event = input.next() => Wake::Input(event),
+        Some(message) = streams.next(), if !streams.is_empty() => Wake::Message(message),
+        _ = posted.notified() => Wake::Posted,
+        _ = tokio::time::sleep(timeout.unwrap_or_default()), if timeout.is_some() => Wake::Timeout,
+      };
+      let streamed = match wake {
+        Wake::Input(Some(event)) => {
+          if self.handle_event(event?, quit).is_some() || *quit {
+            return Ok(());
+          }
+          false
+        }
+        Wake::Input(None) => {
+          *quit = true;
+          return Ok(());
+        }
+        Wake::Message(message) => self.handle_message(message, quit)?,
+        Wake::Posted | Wake::Timeout => false,
+      };
+      let due = self.handle_deadlines(Instant::now());
+      let posted = self.handle_messages(quit)?;
+      if streamed || due || posted || self.quitting || *quit {
+        return Ok(());
+      }
+    }
+  }
+}
@@ -0,0 +1,443 @@
+//! Golden snapshot testing of widget trees.
+//!
+//! A widget is rendered off-screen at a fixed size and compared against a checked-in snapshot file
+//! at `tests/snapshots/<name>.snap`, relative to the crate under test.
+//! Run the tests with `WITUI_BLESS=1` to (re)write the snapshot files from the current output.
+//!
+//! The snapshot file holds the text layer, followed by a style layer with one key per cell
+//! (`.` for unstyled cells) and the legend of the style keys:
+//! ```text
+//! --- text
+//! |┌──┐|
+//! |│Hi│|
+//! |└──┘|
+//! --- style
+//! aaaa
+//! a..a
+//! aaaa
+//! --- legend
+//! a fg=Green bg=- +Bold
+//! ```
+//!
+//! Interactive behaviour is scripted with a [`Driver`], which feeds synthetic input events into a
+//! bare widget or a whole `WiTui` and renders a new frame off-screen after each step.
+
+use std::cell::Ref;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent};
+use crossterm::style::ContentStyle;
+use euclid::default::Size2D;
+
+use crate::backend::HeadlessBackend;
+use crate::canvas::{describe_style, Canvas};
+use crate::pointer::Pointer;
+use crate::render::RenderCtx;
+use crate::tree::Focus;
+use crate::widgets::{ColumnView, EventResult, RenderError, RenderResult, Widget};
+use crate::{Event, KeyCode, KeyModifiers, MouseEventKind, WiTui};
+
+pub const BLESS_ENV: &str = "WITUI_BLESS";
+
+const STYLE_KEYS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+  pub text: Vec<String>,
+  pub style: Vec<String>,
+  pub legend: Vec<(char, String)>,
+}
+
+impl Snapshot {
+  pub fn from_canvas(canvas: &Canvas) -> Self {
+    let mut styles = Vec::<ContentStyle>::new();
+    let mut legend = Vec::new();
+    let mut style_layer = Vec::new();
+    for row in canvas.cells() {
+      let mut line = String::new();
+      for cell in row {
+        if *cell.style() == ContentStyle::default() {
+          line.push('.');
+          continue;
+        }
+        let idx = match styles.iter().position(|style| style == cell.style()) {
+          Some(idx) => idx,
+          None => {
+            styles.push(*cell.style());
+            let key = style_key(styles.len() - 1);
+            legend.push((key, describe_style(cell.style())));
+            styles.len() - 1
+          }
+        };
+        line.push(style_key(idx));
+      }
+      style_layer.push(line);
+    }
+    Self {
+      text: canvas.lines(),
+      style: style_layer,
+      legend,
+    }
+  }
+}
+
+fn style_key(idx: usize) -> char {
+  STYLE_KEYS
+    .chars()
+    .nth(idx)
+    .unwrap_or_else(|| std::char::from_u32(0x100 + idx as u32).unwrap_or('?'))
+}
+
+impl Display for Snapshot {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "--- text")?;
+    for line in self.text.iter() {
+      writeln!(f, "|{}|", line)?;
+    }
+    writeln!(f, "--- style")?;
+    for line in self.style.iter() {
+      writeln!(f, "{}", line)?;
+    }
+    writeln!(f, "--- legend")?;
+    for (key, desc) in self.legend.iter() {
+      writeln!(f, "{} {}", key, desc)?;
+    }
+    Ok(())
+  }
+}
+
+/// Render a widget off-screen at the given size and take a snapshot of the resulting frame.
+pub fn render<W: Widget + ?Sized>(widget: &W, size: Size2D<usize>) -> Result<Snapshot, RenderError> {
+  let ctx = RenderCtx::headless(size);
+  ctx.render_root(widget)?;
+  ctx.renderer().flush();
+  let snapshot = Snapshot::from_canvas(&ctx.canvas());
+  Ok(snapshot)
+}
+
+/// Column of `count` rows, each the row number followed by the text, for content taller than its viewport.
+pub fn numbered_rows(count: usize, text: &str) -> impl Widget {
+  (0..count).fold(ColumnView::new(), |column, row| column.child(format!("{}{}", row, text)))
+}
+
+pub fn snapshot_path(name: &str) -> PathBuf {
+  let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+  PathBuf::from(root)
+    .join("tests")
+    .join("snapshots")
+    .join(format!("{}.snap", name))
+}
+
+/// Render the widget at the given size and compare it against the `name` snapshot file,
+/// panicking with a diff on mismatch. With `WITUI_BLESS` set, the snapshot file is written instead.
+pub fn assert_snapshot<W: Widget + ?Sized>(name: &str, widget: &W, size: Size2D<usize>) {
+  let actual = match render(widget, size) {
+    Ok(snapshot) => snapshot.to_string(),
+    Err(err) => panic!("snapshot `{}`: {}", name, err),
+  };
+  let path = snapshot_path(name);
+
+  if std::env::var_os(BLESS_ENV).is_some() {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, &actual).unwrap();
+    return;
+  }
+
+  let expected = match std::fs::read_to_string(&path) {
+    Ok(expected) => expected,
+    Err(err) => panic!(
+      "snapshot `{}`: cannot read {}: {}\nrun with {}=1 to create it, actual output:\n{}",
+      name,
+      path.display(),
+      err,
+      BLESS_ENV,
+      actual
+    ),
+  };
+
+  if expected != actual {
+    panic!(
+      "snapshot `{}` does not match {}\nrun with {}=1 to update it\n{}",
+      name,
+      path.display(),
+      BLESS_ENV,
+      diff(&expected, &actual)
+    );
+  }
+}
+
+/// Line diff of two texts, prefixing removed lines with `-` and added lines with `+`.
+pub fn diff(expected: &str, actual: &str) -> String {
+  let old = expected.lines().collect::<Vec<_>>();
+  let new = actual.lines().collect::<Vec<_>>();
+
+  // Longest common subsequence table, lcs[i][j] is the LCS length of old[i..] and new[j..]
+  let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lcs[i][j] = if old[i] == new[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+    }
+  }
+
+  let mut out = String::new();
+  let (mut i, mut j) = (0, 0);
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      out.push_str(&format!("  {}\n", old[i]));
+      i += 1;
+      j += 1;
+    } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+      out.push_str(&format!("+ {}\n", new[j]));
+      j += 1;
+    } else {
+      out.push_str(&format!("- {}\n", old[i]));
+      i += 1;
+    }
+  }
+  out
+}
+
+/// Something a [`Driver`] can feed events to and render off-screen.
+pub trait DriverTarget {
+  /// Deliver an input event, returns Unhandled if it was not delivered at all.
+  fn dispatch(&mut self, event: Event) -> EventResult;
+  fn render(&mut self) -> RenderResult;
+  fn render_ctx(&mut self) -> &RenderCtx;
+}
+
+/// A bare widget rendered on a headless context.
+/// Input events are routed as in a WiTui: keys through the focus and mouse events to the widget under the pointer,
+/// except resizes which only resize the context.
+pub struct Headless<W: Widget> {
+  pub widget: W,
+  ctx: RenderCtx,
+  focus: Focus,
+  pointer: Pointer,
+}
+
+impl<W: Widget> DriverTarget for Headless<W> {
+  fn dispatch(&mut self, event: Event) -> EventResult {
+    let size = self.ctx.get_frame().size;
+    match event {
+      Event::Resize(cols, rows) => {
+        self.ctx.resize(cols as usize, rows as usize);
+        EventResult::Unhandled
+      }
+      Event::Key(key) => self
+        .focus
+        .dispatch_key(&mut self.widget, key, &self.ctx.frames(), &size),
+      Event::Mouse(mouse) => self
+        .pointer
+        .dispatch(&mut self.widget, &self.ctx.frames(), mouse, &size, Instant::now()),
+    }
+  }
+
+  fn render(&mut self) -> RenderResult {
+    let size = self.ctx.get_frame().size;
+    self.focus.ensure(&mut self.widget, &self.ctx.frames(), &size);
+    let result = self.ctx.render_root(&self.widget);
+    self.ctx.renderer().flush();
+    result
+  }
+
+  fn render_ctx(&mut self) -> &RenderCtx {
+    &self.ctx
+  }
+}
+
+/// A WiTui receives the events through the same path as in its event loop.
+impl DriverTarget for WiTui {
+  fn dispatch(&mut self, event: Event) -> EventResult {
+    let mut quit = false;
+    self.handle_event(event, &mut quit).unwrap_or(EventResult::Unhandled)
+  }
+
+  fn render(&mut self) -> RenderResult {
+    self.print()
+  }
+
+  fn render_ctx(&mut self) -> &RenderCtx {
+    WiTui::render_ctx(self).expect("the backend of the WiTui failed to set up")
+  }
+}
+
+/// Scripted event simulation for interactive tests.
+///
+/// Each step dispatches its event(s) and then re-renders the frame headlessly,
+/// so the test can assert on both the widget state and the screen contents:
+/// ```no_run
+/// # use euclid::default::Size2D;
+/// # use witui::testing::Driver;
+/// # use witui::widgets::CheckBox;
+/// let mut driver = Driver::new(CheckBox::new(), Size2D::new(1, 1));
+/// driver.click(0, 0);
+/// assert!(driver.widget().is_marked());
+/// assert_eq!(driver.lines(), vec!["◉"]);
+/// ```
+pub struct Driver<T: DriverTarget> {
+  target: T,
+}
+
+impl<W: Widget> Driver<Headless<W>> {
+  pub fn new(widget: W, size: Size2D<usize>) -> Self {
+    let ctx = RenderCtx::headless(size);
+    Self::with_target(Headless {
+      widget,
+      ctx,
+      focus: Focus::default(),
+      pointer: Pointer::default(),
+    })
+  }
+
+  pub fn widget(&self) -> &W {
+    &self.target.widget
+  }
+
+  pub fn widget_mut(&mut self) -> &mut W {
+    &mut self.target.widget
+  }
+}
+
+impl Driver<WiTui> {
+  /// Drive a whole WiTui, which is switched over to a headless backend of the given size.
+  /// The WiTui must not have been rendered yet.
+  pub fn with_tui(tui: WiTui, size: Size2D<usize>) -> Self {
+    Self::with_target(tui.backend(HeadlessBackend::new(size)).alternate(true))
+  }
+
+  pub fn tui(&self) -> &WiTui {
+    &self.target
+  }
+
+  pub fn tui_mut(&mut self) -> &mut WiTui {
+    &mut self.target
+  }
+
+  /// Run the tick and the timers due at the given instant as the event loop would,
+  /// rendering a new frame if any ran. Returns whether any ran.
+  pub fn advance_to(&mut self, now: Instant) -> bool {
+    let due = self.target.handle_deadlines(now);
+    if due {
+      self.render();
+    }
+    due
+  }
+
+  /// Handle what was posted through the WiTui handles as the event loop would,
+  /// rendering a new frame if anything was. Returns whether anything was.
+  pub fn receive(&mut self) -> bool {
+    let mut quit = false;
+    // only the handles post there, there's no input thread
+    let posted = self.target.handle_messages(&mut quit).unwrap_or(true);
+    if posted {
+      self.render();
+    }
+    posted
+  }
+}
+
+impl<T: DriverTarget> Driver<T> {
+  /// Drive any target, rendering its first frame right away.
+  pub fn with_target(target: T) -> Self {
+    let mut this = Self { target };
+    this.render();
+    this
+  }
+
+  pub fn target(&self) -> &T {
+    &self.target
+  }
+
+  pub fn target_mut(&mut self) -> &mut T {
+    &mut self.target
+  }
+
+  /// Dispatch any input event and render the next frame.
+  pub fn send(&mut self, event: Event) -> EventResult {
+    let result = self.target.dispatch(event);
+    self.render();
+    result
+  }
+
+  /// Dispatch a sequence of input events, rendering a frame after each of them.
+  pub fn send_all<I: IntoIterator<Item = Event>>(&mut self, events: I) {
+    for event in events {
+      self.send(event);
+    }
+  }
+
+  pub fn key(&mut self, code: KeyCode) -> EventResult {
+    self.key_with(code, KeyModifiers::NONE)
+  }
+
+  pub fn key_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> EventResult {
+    self.send(Event::Key(KeyEvent::new(code, modifiers)))
+  }
+
+  /// Type each character of the text as a key press, `\n` is typed as Enter.
+  pub fn type_str(&mut self, text: &str) {
+    for char in text.chars() {
+      match char {
+        '\n' => self.key(KeyCode::Enter),
+        char if char.is_uppercase() => self.key_with(KeyCode::Char(char), KeyModifiers::SHIFT),
+        char => self.key(KeyCode::Char(char)),
+      };
+    }
+  }
+
+  /// Left click at the given screen coordinates.
+  pub fn click(&mut self, x: u16, y: u16) -> EventResult {
+    self.click_with(MouseButton::Left, x, y)
+  }
+
+  /// Press and release a mouse button at the given screen coordinates,
+  /// returns the result of the button press.
+  pub fn click_with(&mut self, button: MouseButton, x: u16, y: u16) -> EventResult {
+    let result = self.mouse(MouseEventKind::Down(button), x, y);
+    self.mouse(MouseEventKind::Up(button), x, y);
+    result
+  }
+
+  /// Press a mouse button at `from`, drag it to `to` and release it there, returns the result of the drag.
+  pub fn drag(&mut self, button: MouseButton, from: (u16, u16), to: (u16, u16)) -> EventResult {
+    self.mouse(MouseEventKind::Down(button), from.0, from.1);
+    let result = self.mouse(MouseEventKind::Drag(button), to.0, to.1);
+    self.mouse(MouseEventKind::Up(button), to.0, to.1);
+    result
+  }
+
+  pub fn mouse(&mut self, kind: MouseEventKind, x: u16, y: u16) -> EventResult {
+    self.send(Event::Mouse(MouseEvent {
+      kind,
+      column: x,
+      row: y,
+      modifiers: KeyModifiers::NONE,
+    }))
+  }
+
+  pub fn resize(&mut self, width: u16, height: u16) -> EventResult {
+    self.send(Event::Resize(width, height))
+  }
+
+  /// Render a new frame, panicking if the target fails to render.
+  pub fn render(&mut self) {
+    if let Err(err) = self.target.render() {
+      panic!("driver: {}", err);
+    }
+  }
+
+  /// Canvas holding the last rendered frame.
+  pub fn canvas(&mut self) -> Ref<'_, Canvas> {
+    self.target.render_ctx().canvas()
+  }
+
+  pub fn lines(&mut self) -> Vec<String> {
+    self.canvas().lines()
+  }
+
+  pub fn snapshot(&mut self) -> Snapshot {
+    Snapshot::from_canvas(&self.canvas())
+  }
+}
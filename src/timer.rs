@@ -0,0 +1,130 @@
+//! Callbacks scheduled on the event loop.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+struct Timer {
+  id: TimerId,
+  deadline: Instant,
+  interval: Option<Duration>,
+  callback: Box<dyn FnMut()>,
+}
+
+#[derive(Default)]
+struct Queue {
+  next_id: u64,
+  timers: Vec<Timer>,
+  /// Timer whose callback is running, out of the queue meanwhile, and whether it got cancelled.
+  running: Option<(TimerId, bool)>,
+  /// Instant the delays start from, instead of the current one, when replaying on the recorded clock.
+  clock: Option<Instant>,
+}
+
+/// Handle to the timers of a WiTui, clone it into the widgets or closures that schedule callbacks.
+/// Callbacks run on the event loop and a new frame is rendered after them.
+#[derive(Clone, Default)]
+pub struct Timers {
+  queue: Rc<RefCell<Queue>>,
+}
+
+impl Timers {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Run the callback once, after the delay.
+  pub fn after<F: FnMut() + 'static>(&self, delay: Duration, callback: F) -> TimerId {
+    self.schedule(delay, None, Box::new(callback))
+  }
+
+  /// Run the callback every interval, until cancelled.
+  pub fn every<F: FnMut() + 'static>(&self, interval: Duration, callback: F) -> TimerId {
+    self.schedule(interval, Some(interval), Box::new(callback))
+  }
+
+  /// Cancel a timer, callbacks may cancel their own timer.
+  pub fn cancel(&self, id: TimerId) {
+    let mut queue = self.queue.borrow_mut();
+    queue.timers.retain(|timer| timer.id != id);
+    if let Some((running, cancelled)) = queue.running.as_mut() {
+      if *running == id {
+        *cancelled = true;
+      }
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.queue.borrow().timers.is_empty()
+  }
+
+  /// Deadline of the timer due first.
+  pub fn next_deadline(&self) -> Option<Instant> {
+    self.queue.borrow().timers.iter().map(|timer| timer.deadline).min()
+  }
+
+  /// Run the callbacks of the timers due at the given instant, returns how many ran.
+  /// The event loop calls this, it is also the way to drive the timers in tests.
+  /// A repeating timer runs at most once per call, missed intervals are skipped.
+  pub fn fire_due(&self, now: Instant) -> usize {
+    let mut fired = 0;
+    loop {
+      let mut timer = {
+        let mut queue = self.queue.borrow_mut();
+        let due = queue
+          .timers
+          .iter()
+          .enumerate()
+          .filter(|(_, timer)| timer.deadline <= now)
+          .min_by_key(|(_, timer)| timer.deadline)
+          .map(|(idx, _)| idx);
+        match due {
+          Some(idx) => {
+            let timer = queue.timers.remove(idx);
+            queue.running = Some((timer.id, false));
+            timer
+          }
+          None => break,
+        }
+      };
+      (timer.callback)();
+      fired += 1;
+
+      let mut queue = self.queue.borrow_mut();
+      let cancelled = matches!(queue.running.take(), Some((_, true)));
+      if let (Some(interval), false) = (timer.interval, cancelled) {
+        while timer.deadline <= now {
+          timer.deadline += interval.max(Duration::from_millis(1));
+        }
+        queue.timers.push(timer);
+      }
+    }
+    fired
+  }
+
+  pub(crate) fn set_clock(&self, now: Instant) {
+    self.queue.borrow_mut().clock = Some(now);
+  }
+
+  /// Current instant on the clock the timers run on, the recorded one when replaying.
+  pub(crate) fn now(&self) -> Instant {
+    self.queue.borrow().clock.unwrap_or_else(Instant::now)
+  }
+
+  fn schedule(&self, delay: Duration, interval: Option<Duration>, callback: Box<dyn FnMut()>) -> TimerId {
+    let mut queue = self.queue.borrow_mut();
+    let id = TimerId(queue.next_id);
+    queue.next_id += 1;
+    let deadline = queue.clock.unwrap_or_else(Instant::now) + delay;
+    queue.timers.push(Timer {
+      id,
+      deadline,
+      interval,
+      callback,
+    });
+    id
+  }
+}
@@ -0,0 +1,409 @@
+//! Walking the widget tree through `Widget::visit_children`.
+//! Widgets are addressed by their path, the child indexes from the root down to the widget.
+//!
+//! ```
+//! # use witui::widgets::{ColumnView, Widget};
+//! let root = ColumnView::new().child("one").child(ColumnView::new().child("two"));
+//! let mut paths = Vec::new();
+//! witui::tree::walk(&root, &mut |_, path| paths.push(path.to_vec()));
+//! assert_eq!(paths, vec![vec![], vec![0], vec![1], vec![1, 0]]);
+//! ```
+
+use std::collections::HashMap;
+
+use crossterm::event::KeyEvent;
+use euclid::default::{Point2D, Rect, Size2D};
+
+use crate::widgets::{AnyEvent, Capability, EventResult, Widget, WidgetId};
+use crate::{Event, KeyCode};
+
+pub(crate) type Path = Vec<usize>;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WidgetFrame {
+  /// Frame given to the widget, event coordinates are relative to its origin.
+  pub frame: Rect<usize>,
+  /// Part of the frame that made it to the screen, where the widget can be hit.
+  pub visible: Rect<usize>,
+}
+
+/// Frames the widgets were rendered into by their path, recorded by the RenderCtx on each child render.
+/// A widget rendered multiple times in a frame, like the child of a `Repeat`, has all its frames kept.
+#[derive(Default)]
+pub(crate) struct Frames {
+  frames: HashMap<Path, Vec<WidgetFrame>>,
+}
+
+impl Frames {
+  pub(crate) fn record(&mut self, path: Path, frame: Rect<usize>, visible: Rect<usize>) {
+    self
+      .frames
+      .entry(path)
+      .or_default()
+      .push(WidgetFrame { frame, visible });
+  }
+
+  pub(crate) fn get(&self, path: &[usize]) -> &[WidgetFrame] {
+    self.frames.get(path).map(|frames| frames.as_slice()).unwrap_or(&[])
+  }
+
+  /// Take over the frames recorded on another canvas, drawn here with its `origin` point moved to `to`
+  /// and clipped to `visible`. Frames moved past the top or left edge are clamped to it.
+  pub(crate) fn append(&mut self, other: Frames, origin: Point2D<usize>, to: Point2D<usize>, visible: &Rect<usize>) {
+    let shift = |point: Point2D<usize>| {
+      Point2D::new(
+        (point.x + to.x).saturating_sub(origin.x),
+        (point.y + to.y).saturating_sub(origin.y),
+      )
+    };
+    for (path, frames) in other.frames {
+      let moved = frames.into_iter().map(|frame| {
+        let visible_at = shift(frame.visible.origin);
+        let visible = visible
+          .intersection(&Rect::new(visible_at, frame.visible.size))
+          .unwrap_or(Rect::new(visible_at, Size2D::zero()));
+        WidgetFrame {
+          frame: Rect::new(shift(frame.frame.origin), frame.frame.size),
+          visible,
+        }
+      });
+      self.frames.entry(path).or_default().extend(moved);
+    }
+  }
+
+  /// Frames of the widgets below the one at path, by their path from it.
+  pub(crate) fn below(&self, path: &[usize]) -> Frames {
+    let frames = self
+      .frames
+      .iter()
+      .filter_map(|(below, frames)| Some((below.strip_prefix(path)?.to_vec(), frames.clone())))
+      .collect();
+    Frames { frames }
+  }
+
+  /// Size of the first frame the widget at path was rendered into, if it was rendered.
+  pub(crate) fn size_of(&self, path: &[usize]) -> Option<Size2D<usize>> {
+    self.get(path).first().map(|frame| frame.frame.size)
+  }
+}
+
+/// Call `f` on the widget at the given path below root, if there is one.
+pub(crate) fn with_widget_mut<R, F>(root: &mut dyn Widget, path: &[usize], f: F) -> Option<R>
+where
+  F: FnOnce(&mut dyn Widget) -> R,
+{
+  let (first, rest) = match path.split_first() {
+    Some(split) => split,
+    None => return Some(f(root)),
+  };
+  let mut f = Some(f);
+  let mut result = None;
+  let mut idx = 0;
+  root.visit_children_mut(&mut |child| {
+    if idx == *first {
+      if let Some(f) = f.take() {
+        result = with_widget_mut(child, rest, f);
+      }
+    }
+    idx += 1;
+  });
+  result
+}
+
+/// Call the visitor on the root and each of its descendants along with their path, depth-first,
+/// parents before their children.
+pub fn walk(root: &dyn Widget, visitor: &mut dyn FnMut(&dyn Widget, &[usize])) {
+  walk_below(root, &mut Vec::new(), visitor);
+}
+
+fn walk_below(widget: &dyn Widget, path: &mut Path, visitor: &mut dyn FnMut(&dyn Widget, &[usize])) {
+  visitor(widget, path);
+  let mut idx = 0;
+  widget.visit_children(&mut |child| {
+    path.push(idx);
+    walk_below(child, path, visitor);
+    path.pop();
+    idx += 1;
+  });
+}
+
+/// Same as `walk`, with the widgets lent mutably.
+pub fn walk_mut(root: &mut dyn Widget, visitor: &mut dyn FnMut(&mut dyn Widget, &[usize])) {
+  walk_below_mut(root, &mut Vec::new(), visitor);
+}
+
+fn walk_below_mut(widget: &mut dyn Widget, path: &mut Path, visitor: &mut dyn FnMut(&mut dyn Widget, &[usize])) {
+  visitor(widget, path);
+  let mut idx = 0;
+  widget.visit_children_mut(&mut |child| {
+    path.push(idx);
+    walk_below_mut(child, path, visitor);
+    path.pop();
+    idx += 1;
+  });
+}
+
+/// Path to the first widget with the ID, in depth-first order.
+pub(crate) fn find(root: &dyn Widget, id: &WidgetId) -> Option<Path> {
+  if root.id().as_ref() == Some(id) {
+    return Some(Path::new());
+  }
+  let mut found = None;
+  let mut idx = 0;
+  root.visit_children(&mut |child| {
+    if found.is_none() {
+      found = find(child, id).map(|mut path| {
+        path.insert(0, idx);
+        path
+      });
+    }
+    idx += 1;
+  });
+  found
+}
+
+/// The widget at the given path below root, if every container along it keeps its children.
+pub(crate) fn widget<'w>(root: &'w dyn Widget, path: &[usize]) -> Option<&'w dyn Widget> {
+  path.iter().try_fold(root, |widget, idx| widget.get_child(*idx))
+}
+
+pub(crate) fn widget_mut<'w>(root: &'w mut dyn Widget, path: &[usize]) -> Option<&'w mut dyn Widget> {
+  path.iter().try_fold(root, |widget, idx| widget.get_child_mut(*idx))
+}
+
+/// Paths of the focusable widgets in depth-first order.
+/// A selectable widget is only focusable when none of its descendants are,
+/// so decorators forwarding `has_capability` to their child do not take the focus themselves.
+pub(crate) fn focus_chain(root: &dyn Widget) -> Vec<Path> {
+  let mut chain = Vec::new();
+  collect_focusable(root, &mut Vec::new(), &mut chain);
+  chain
+}
+
+fn collect_focusable(widget: &dyn Widget, path: &mut Path, chain: &mut Vec<Path>) {
+  let before = chain.len();
+  let mut idx = 0;
+  widget.visit_children(&mut |child| {
+    path.push(idx);
+    collect_focusable(child, path, chain);
+    path.pop();
+    idx += 1;
+  });
+  if chain.len() == before && widget.has_capability(&Capability::Selectable) {
+    chain.push(path.clone());
+  }
+}
+
+/// Child indexes from a widget down to its deepest descendant rendered under the point, each with its frame.
+/// Children rendered without going through the RenderCtx have no frame and take their parent's one.
+type Hit = Vec<(usize, Option<Rect<usize>>)>;
+
+fn hit_below(widget: &dyn Widget, path: &mut Path, frames: &Frames, point: &Point2D<usize>) -> Option<Hit> {
+  let mut hit = None;
+  let mut idx = 0;
+  widget.visit_children(&mut |child| {
+    path.push(idx);
+    let child_frames = frames.get(path);
+    let found = if child_frames.is_empty() {
+      hit_below(child, path, frames, point).map(|below| (None, below))
+    } else {
+      child_frames
+        .iter()
+        .find(|frame| frame.visible.contains(*point))
+        .map(|frame| {
+          (
+            Some(frame.frame),
+            hit_below(child, path, frames, point).unwrap_or_default(),
+          )
+        })
+    };
+    path.pop();
+    // later children are drawn over the earlier ones
+    if let Some((frame, below)) = found {
+      let mut steps = vec![(idx, frame)];
+      steps.extend(below);
+      hit = Some(steps);
+    }
+    idx += 1;
+  });
+  hit
+}
+
+/// Path to the deepest widget under the point, with the frame of each widget along it, starting with the root's.
+pub(crate) fn hit(
+  root: &dyn Widget, frames: &Frames, point: &Point2D<usize>, size: &Size2D<usize>,
+) -> (Path, Vec<Rect<usize>>) {
+  let mut path = Path::new();
+  let mut rects = vec![Rect::from_size(*size)];
+  for (idx, frame) in hit_below(root, &mut Path::new(), frames, point).unwrap_or_default() {
+    let parent = rects[rects.len() - 1];
+    path.push(idx);
+    rects.push(frame.unwrap_or(parent));
+  }
+  (path, rects)
+}
+
+/// Point relative to the frame origin.
+pub(crate) fn relative(point: &Point2D<usize>, frame: &Rect<usize>) -> Point2D<usize> {
+  Point2D::new(
+    point.x.saturating_sub(frame.min_x()),
+    point.y.saturating_sub(frame.min_y()),
+  )
+}
+
+/// Send an event from the widget at path up to the root while it is unhandled,
+/// each widget gets the event made for its frame along with the frame size.
+/// Returns the result along with the depth of the widget that handled the event.
+pub(crate) fn bubble<F>(
+  root: &mut dyn Widget, path: &[usize], rects: &[Rect<usize>], event: F,
+) -> (EventResult, Option<usize>)
+where
+  F: Fn(&Rect<usize>) -> AnyEvent,
+{
+  for depth in (0..=path.len()).rev() {
+    let frame = rects[depth];
+    let event = event(&frame);
+    match with_widget_mut(root, &path[..depth], |widget| widget.event(&event, &frame.size)) {
+      None | Some(EventResult::Unhandled) => continue,
+      Some(result) => return (result, Some(depth)),
+    }
+  }
+  (EventResult::Unhandled, None)
+}
+
+/// Send an event to every widget of the tree, parents before their children.
+pub(crate) fn broadcast(root: &mut dyn Widget, event: &AnyEvent, frames: &Frames, size: &Size2D<usize>) {
+  walk_mut(root, &mut |widget, path| {
+    widget.event(event, &frames.size_of(path).unwrap_or(*size));
+  });
+}
+
+/// Send an event to the widgets of the tree, children before their parents, until one handles it.
+pub(crate) fn offer(root: &mut dyn Widget, event: &AnyEvent, frames: &Frames, size: &Size2D<usize>) -> EventResult {
+  offer_below(root, &mut Path::new(), event, frames, size)
+}
+
+fn offer_below(
+  widget: &mut dyn Widget, path: &mut Path, event: &AnyEvent, frames: &Frames, size: &Size2D<usize>,
+) -> EventResult {
+  let mut result = EventResult::Unhandled;
+  let mut idx = 0;
+  widget.visit_children_mut(&mut |child| {
+    if let EventResult::Unhandled = result {
+      path.push(idx);
+      result = offer_below(child, path, event, frames, size);
+      path.pop();
+    }
+    idx += 1;
+  });
+  if let EventResult::Unhandled = result {
+    result = widget.event(event, &frames.size_of(path).unwrap_or(*size));
+  }
+  result
+}
+
+/// Keyboard focus over a widget tree.
+/// Keys go to the focused widget, Tab and BackTab move the focus along the focus chain.
+#[derive(Default)]
+pub(crate) struct Focus {
+  path: Option<Path>,
+}
+
+impl Focus {
+  /// Focus the first focusable widget if the focused one is gone from the tree, or none is focused yet.
+  pub(crate) fn ensure(&mut self, root: &mut dyn Widget, frames: &Frames, size: &Size2D<usize>) {
+    let chain = focus_chain(root);
+    self.ensure_in(root, &chain, frames, size);
+  }
+
+  fn ensure_in(&mut self, root: &mut dyn Widget, chain: &[Path], frames: &Frames, size: &Size2D<usize>) {
+    match self.path.as_ref() {
+      Some(path) if chain.contains(path) => {}
+      _ => match chain.first() {
+        Some(first) => self.focus(root, first.clone(), frames, size),
+        None => self.path = None,
+      },
+    }
+  }
+
+  /// Tell the focused widget again it has the focus, after the tree was rebuilt,
+  /// or focus the first focusable widget if there is none at the focused path anymore.
+  pub(crate) fn refocus(&mut self, root: &mut dyn Widget, frames: &Frames, size: &Size2D<usize>) {
+    let chain = focus_chain(root);
+    match self.path.take() {
+      Some(path) if chain.contains(&path) => {
+        focus_in(root, &path, frames, size);
+        self.path = Some(path);
+      }
+      _ => self.ensure_in(root, &chain, frames, size),
+    }
+  }
+
+  pub(crate) fn focus(&mut self, root: &mut dyn Widget, path: Path, frames: &Frames, size: &Size2D<usize>) {
+    if self.path.as_ref() == Some(&path) {
+      return;
+    }
+    if let Some(old) = self.path.take() {
+      send_sized(root, &old, &AnyEvent::FocusOut, frames, size);
+    }
+    focus_in(root, &path, frames, size);
+    self.path = Some(path);
+  }
+
+  /// Deliver a key to the focused widget, then up to the root while it is unhandled.
+  /// If nothing in the tree is focusable, the key is offered to every widget, children first.
+  pub(crate) fn dispatch_key(
+    &mut self, root: &mut dyn Widget, key: KeyEvent, frames: &Frames, size: &Size2D<usize>,
+  ) -> EventResult {
+    let chain = focus_chain(root);
+    if chain.is_empty() {
+      self.path = None;
+      return offer(root, &AnyEvent::Input(Event::Key(key)), frames, size);
+    }
+
+    let current = self.path.as_ref().and_then(|path| chain.iter().position(|p| p == path));
+    let next = match key.code {
+      KeyCode::Tab => Some(current.map(|idx| (idx + 1) % chain.len()).unwrap_or(0)),
+      KeyCode::BackTab => Some(current.and_then(|idx| idx.checked_sub(1)).unwrap_or(chain.len() - 1)),
+      _ => None,
+    };
+    if let Some(next) = next {
+      self.focus(root, chain[next].clone(), frames, size);
+      return EventResult::Done;
+    }
+
+    self.ensure_in(root, &chain, frames, size);
+    let path = self.path.clone().unwrap_or_default();
+    bubble_sized(root, &path, &AnyEvent::Input(Event::Key(key)), frames, size)
+  }
+}
+
+/// Tell the widget at path it has the focus, then its ancestors that the focus is below them.
+fn focus_in(root: &mut dyn Widget, path: &[usize], frames: &Frames, size: &Size2D<usize>) {
+  send_sized(root, path, &AnyEvent::FocusIn, frames, size);
+  for depth in (0..path.len()).rev() {
+    send_sized(root, &path[..depth], &AnyEvent::FocusWithin(path[depth..].to_vec()), frames, size);
+  }
+}
+
+/// Send an event from the widget at path up to the root while it is unhandled, each sized as with `send_sized`.
+pub(crate) fn bubble_sized(
+  root: &mut dyn Widget, path: &[usize], event: &AnyEvent, frames: &Frames, size: &Size2D<usize>,
+) -> EventResult {
+  for depth in (0..=path.len()).rev() {
+    match send_sized(root, &path[..depth], event, frames, size) {
+      None | Some(EventResult::Unhandled) => continue,
+      Some(result) => return result,
+    }
+  }
+  EventResult::Unhandled
+}
+
+/// Send an event to the widget at path, sized by the frame it was last rendered into,
+/// or by the given size if it was not rendered.
+pub(crate) fn send_sized(
+  root: &mut dyn Widget, path: &[usize], event: &AnyEvent, frames: &Frames, size: &Size2D<usize>,
+) -> Option<EventResult> {
+  let size = frames.size_of(path).unwrap_or(*size);
+  with_widget_mut(root, path, |widget| widget.event(event, &size))
+}
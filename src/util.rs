@@ -1,6 +1,8 @@
 use std::ops::{Deref, DerefMut};
 
 use crossterm::event::KeyCode::Delete;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 pub enum Scoped<'r, Type: ?Sized> {
   Ref(&'r Type),
@@ -92,3 +94,68 @@ pub trait Immutable {
     Immut::new(self)
   }
 }
+
+/// Number of terminal cells taken by a grapheme cluster: 0, 1 or 2.
+pub fn grapheme_width(grapheme: &str) -> usize {
+  grapheme.width().min(2)
+}
+
+/// Number of terminal cells taken by a string, as laid out on the Canvas.
+pub fn display_width(str: &str) -> usize {
+  str.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Longest prefix of the string that fits in the given number of cells, and its display width.
+/// Graphemes are never split, so a wide grapheme that does not fit entirely is left out.
+pub fn truncate(str: &str, width: usize) -> (&str, usize) {
+  let mut used = 0;
+  for (idx, grapheme) in str.grapheme_indices(true) {
+    let grapheme_width = grapheme_width(grapheme);
+    if used + grapheme_width > width {
+      return (&str[..idx], used);
+    }
+    used += grapheme_width;
+  }
+  (str, used)
+}
+
+/// Blocks filling the bottom of a cell by eighths.
+pub(crate) const LOWER_EIGHTHS: [char; 7] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇'];
+/// Blocks filling the left of a cell by eighths.
+pub(crate) const LEFT_EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// How a cell along a track is covered by a span of the track, for bars drawn to the eighth of a cell.
+pub(crate) enum Coverage {
+  Empty,
+  Full,
+  /// Drawn with the block of that many eighths, reversed when the span covers the other part of the cell.
+  Partial { eighths: usize, reversed: bool },
+}
+
+/// Eighths of the cell at the index along a track covered by the span, given in eighths of a cell.
+pub(crate) fn covered(idx: usize, (start, end): (usize, usize)) -> usize {
+  end.min(idx * 8 + 8).saturating_sub(start.max(idx * 8))
+}
+
+/// Coverage of the cell at the index along a track by the span, the blocks filling the cells
+/// from their side towards the lower indexes if `blocks_at_head`, otherwise from the other side.
+pub(crate) fn coverage(idx: usize, span: (usize, usize), blocks_at_head: bool) -> Coverage {
+  match covered(idx, span) {
+    0 => Coverage::Empty,
+    8 => Coverage::Full,
+    covered => {
+      let span_at_head = span.0 <= idx * 8;
+      if span_at_head == blocks_at_head {
+        Coverage::Partial {
+          eighths: covered,
+          reversed: false,
+        }
+      } else {
+        Coverage::Partial {
+          eighths: 8 - covered,
+          reversed: true,
+        }
+      }
+    }
+  }
+}
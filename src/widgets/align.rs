@@ -6,7 +6,7 @@ use euclid::default::Size2D;
 use euclid::SideOffsets2D;
 
 use crate::render::RenderCtx;
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutResult, RenderError, RenderResult, Widget};
+use crate::widgets::{LayoutResult, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 use crate::{HorizontalAlignment, HorizontalSide, VerticalAlignment, VerticalSide};
 
@@ -111,10 +111,6 @@ impl<Child> Widget for Align<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     self.child.layout(avail_size)
   }
@@ -176,7 +172,11 @@ where
     ctx.render_child_widget(child_frame, &self.child)
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
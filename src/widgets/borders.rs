@@ -1,12 +1,13 @@
 use crossterm::event::Event;
-use euclid::default::{Rect, Size2D};
-use euclid::SideOffsets2D;
+use euclid::default::{Rect, SideOffsets2D, Size2D};
 
 use crate::render::{RenderCtx, Renderer};
 use crate::widgets::fillchar::FillChar;
 use crate::widgets::repeat::Repeat;
+use crate::widgets::scrollbar::{self, Scrollbar};
 use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
+  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, Orientation, RenderError, RenderResult,
+  ScrollPosition, Widget,
 };
 use crate::Style;
 
@@ -25,6 +26,9 @@ pub struct Borders<Border, Child> {
   pub top_right: Option<Border>,
   pub bottom_left: Option<Border>,
   pub bottom_right: Option<Border>,
+  // scrollbars taking the place of the right and bottom sides, following the child's scroll position
+  pub right_scrollbar: Option<Scrollbar>,
+  pub bottom_scrollbar: Option<Scrollbar>,
   // child
   pub child: Child,
 }
@@ -40,6 +44,8 @@ impl Borders<Box<dyn Widget>, ()> {
       top_right: None,
       bottom_left: None,
       bottom_right: None,
+      right_scrollbar: None,
+      bottom_scrollbar: None,
       child: (),
     }
   }
@@ -60,6 +66,8 @@ where
       top_right: None,
       bottom_left: None,
       bottom_right: None,
+      right_scrollbar: None,
+      bottom_scrollbar: None,
       child,
     }
   }
@@ -74,6 +82,8 @@ where
       top_right: self.top_right,
       bottom_left: self.bottom_left,
       bottom_right: self.bottom_right,
+      right_scrollbar: self.right_scrollbar,
+      bottom_scrollbar: self.bottom_scrollbar,
       child,
     }
   }
@@ -98,6 +108,20 @@ where
     self
   }
 
+  /// Draw the right side as a vertical scrollbar following the child's scroll position, scrolling it when used.
+  pub fn scrollbar_right(mut self, mut bar: Scrollbar) -> Self {
+    bar.orientation = Orientation::Vertical;
+    self.right_scrollbar = Some(bar);
+    self
+  }
+
+  /// Draw the bottom side as a horizontal scrollbar following the child's scroll position, scrolling it when used.
+  pub fn scrollbar_bottom(mut self, mut bar: Scrollbar) -> Self {
+    bar.orientation = Orientation::Horizontal;
+    self.bottom_scrollbar = Some(bar);
+    self
+  }
+
   pub fn top_left(mut self, border: Border) -> Self {
     self.top_left = Some(border);
     self
@@ -197,19 +221,74 @@ where
   }
 }
 
+impl<Border, Child> Borders<Border, Child> {
+  fn has_right(&self) -> bool {
+    self.right.is_some() || self.right_scrollbar.is_some()
+  }
+
+  fn has_bottom(&self) -> bool {
+    self.bottom.is_some() || self.bottom_scrollbar.is_some()
+  }
+
+  /// Width of each side, one cell for the sides drawn.
+  fn sides(&self) -> SideOffsets2D<usize> {
+    SideOffsets2D::new(
+      self.top.is_some() as usize,
+      self.has_right() as usize,
+      self.has_bottom() as usize,
+      self.left.is_some() as usize,
+    )
+  }
+}
+
+impl<Border, Child> Borders<Border, Child>
+where
+  Border: Widget,
+  Child: Widget,
+{
+  /// Frame of the child within a frame of the given size, the sides hugging the child when it takes less.
+  fn child_frame(&self, size: &Size2D<usize>) -> Result<Rect<usize>, LayoutError> {
+    let layout = self.layout(size)?;
+    Ok(Rect::from_size(layout.max.min(*size)).inner_rect(self.sides()))
+  }
+}
+
 impl<Border, Child> Widget for Borders<Border, Child>
 where
   Border: Widget,
   Child: Widget,
 {
   fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    self.child.event(event, size)
+    if self.right_scrollbar.is_none() && self.bottom_scrollbar.is_none() {
+      return EventResult::Unhandled;
+    }
+    let child = match self.child_frame(size) {
+      Ok(child) => child,
+      Err(_) => return EventResult::Unhandled,
+    };
+    let sides = self.sides();
+    let frame = child.outer_rect(sides);
+    let (top_offset, right_offset, bottom_offset, left_offset) = (sides.top, sides.right, sides.bottom, sides.left);
+
+    if let Some(bar) = self.right_scrollbar.as_mut() {
+      let bar_frame = frame.inner_rect(SideOffsets2D::new(top_offset, 0, bottom_offset, frame.width() - 1));
+      if let Some(result) = scrollbar::event_attached(bar, &mut self.child, &child.size, &bar_frame, event) {
+        return result;
+      }
+    }
+    if let Some(bar) = self.bottom_scrollbar.as_mut() {
+      let bar_frame = frame.inner_rect(SideOffsets2D::new(frame.height() - 1, right_offset, 0, left_offset));
+      if let Some(result) = scrollbar::event_attached(bar, &mut self.child, &child.size, &bar_frame, event) {
+        return result;
+      }
+    }
+    EventResult::Unhandled
   }
 
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     debug!("layout() : avail_size: {:?}", avail_size);
-    let borders_width = if self.left.is_some() { 1 } else { 0 } + if self.right.is_some() { 1 } else { 0 };
-    let borders_height = if self.top.is_some() { 1 } else { 0 } + if self.bottom.is_some() { 1 } else { 0 };
+    let borders_width = if self.left.is_some() { 1 } else { 0 } + if self.has_right() { 1 } else { 0 };
+    let borders_height = if self.top.is_some() { 1 } else { 0 } + if self.has_bottom() { 1 } else { 0 };
 
     let mut size = avail_size.clone();
     if size.width < borders_width || size.height < borders_height {
@@ -219,8 +298,8 @@ where
 
     let top_offset = if self.top.is_some() { 1 } else { 0 };
     let left_offset = if self.left.is_some() { 1 } else { 0 };
-    let right_offset = if self.right.is_some() { 1 } else { 0 };
-    let bottom_offset = if self.bottom.is_some() { 1 } else { 0 };
+    let right_offset = if self.has_right() { 1 } else { 0 };
+    let bottom_offset = if self.has_bottom() { 1 } else { 0 };
 
     if let Some(top) = self.top.as_ref() {
       let border_frame = frame.inner_rect(SideOffsets2D::new(0, right_offset, frame.height() - 1, left_offset));
@@ -257,16 +336,13 @@ where
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
     let frame = ctx.get_frame().clone();
     debug!("render() : frame: {:?}", &frame);
-    let mut layout = self.layout(&frame.size).map_err(|e| RenderError::Layout(e))?;
-
-    layout.max.width = std::cmp::min(layout.max.width, frame.size.width);
-    layout.max.height = std::cmp::min(layout.max.height, frame.size.height);
-    let frame = Rect::new(frame.origin, layout.max);
-
-    let top_offset = if self.top.is_some() { 1 } else { 0 };
-    let left_offset = if self.left.is_some() { 1 } else { 0 };
-    let right_offset = if self.right.is_some() { 1 } else { 0 };
-    let bottom_offset = if self.bottom.is_some() { 1 } else { 0 };
+    let child_frame = self
+      .child_frame(&frame.size)
+      .map_err(|e| RenderError::Layout(e))?
+      .translate(frame.origin.to_vector());
+    let sides = self.sides();
+    let frame = child_frame.outer_rect(sides);
+    let (top_offset, right_offset, bottom_offset, left_offset) = (sides.top, sides.right, sides.bottom, sides.left);
 
     if let Some(top) = self.top.as_ref() {
       let border_frame = frame.inner_rect(SideOffsets2D::new(0, right_offset, frame.height() - 1, left_offset));
@@ -276,13 +352,21 @@ where
       let border_frame = frame.inner_rect(SideOffsets2D::new(top_offset, frame.width() - 1, bottom_offset, 0));
       ctx.render_child_widget(border_frame, left)?;
     }
-    if let Some(right) = self.right.as_ref() {
+    if self.has_right() {
       let border_frame = frame.inner_rect(SideOffsets2D::new(top_offset, 0, bottom_offset, frame.width() - 1));
-      ctx.render_child_widget(border_frame, right)?;
+      match (self.right_scrollbar.as_ref(), self.right.as_ref()) {
+        (Some(bar), _) => scrollbar::render_attached(bar, &self.child, &child_frame.size, border_frame, ctx)?,
+        (None, Some(right)) => ctx.render_child_widget(border_frame, right)?,
+        (None, None) => {}
+      }
     }
-    if let Some(bottom) = self.bottom.as_ref() {
+    if self.has_bottom() {
       let border_frame = frame.inner_rect(SideOffsets2D::new(frame.height() - 1, right_offset, 0, left_offset));
-      ctx.render_child_widget(border_frame, bottom)?;
+      match (self.bottom_scrollbar.as_ref(), self.bottom.as_ref()) {
+        (Some(bar), _) => scrollbar::render_attached(bar, &self.child, &child_frame.size, border_frame, ctx)?,
+        (None, Some(bottom)) => ctx.render_child_widget(border_frame, bottom)?,
+        (None, None) => {}
+      }
     }
 
     if self.top.is_some() && self.left.is_some() {
@@ -292,32 +376,89 @@ where
       }
     }
 
-    if self.top.is_some() && self.right.is_some() {
+    if self.top.is_some() && self.has_right() {
       if let Some(top_right) = self.top_right.as_ref() {
         let border_frame = frame.inner_rect(SideOffsets2D::new(0, 0, frame.height() - 1, frame.width() - 1));
         ctx.render_child_widget(border_frame, top_right)?;
       }
     }
 
-    if self.bottom.is_some() && self.left.is_some() {
+    if self.has_bottom() && self.left.is_some() {
       if let Some(bottom_left) = self.bottom_left.as_ref() {
         let border_frame = frame.inner_rect(SideOffsets2D::new(frame.height() - 1, frame.width() - 1, 0, 0));
         ctx.render_child_widget(border_frame, bottom_left)?;
       }
     }
 
-    if self.bottom.is_some() && self.right.is_some() {
+    if self.has_bottom() && self.has_right() {
       if let Some(bottom_right) = self.bottom_right.as_ref() {
         let border_frame = frame.inner_rect(SideOffsets2D::new(frame.height() - 1, 0, 0, frame.width() - 1));
         ctx.render_child_widget(border_frame, bottom_right)?;
       }
     }
 
-    let child_frame = frame.inner_rect(SideOffsets2D::new(top_offset, right_offset, bottom_offset, left_offset));
     ctx.render_child_widget(child_frame, &self.child)
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
     self.child.has_capability(capability)
   }
+
+  fn scroll_position(&self, orientation: Orientation, size: &Size2D<usize>) -> Option<ScrollPosition> {
+    self.child.scroll_position(orientation, &self.child_frame(size).ok()?.size)
+  }
+
+  fn scroll_to(&mut self, orientation: Orientation, offset: usize, size: &Size2D<usize>) {
+    if let Ok(child) = self.child_frame(size) {
+      self.child.scroll_to(orientation, offset, &child.size)
+    }
+  }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    let borders = [&self.top_left, &self.top, &self.top_right, &self.left];
+    for border in borders.iter().filter_map(|border| border.as_ref()) {
+      visitor(border);
+    }
+    visitor(&self.child);
+    let borders = [&self.right, &self.bottom_left, &self.bottom, &self.bottom_right];
+    for border in borders.iter().filter_map(|border| border.as_ref()) {
+      visitor(border);
+    }
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    let mut borders = [&mut self.top_left, &mut self.top, &mut self.top_right, &mut self.left];
+    for border in borders.iter_mut().filter_map(|border| border.as_mut()) {
+      visitor(border);
+    }
+    visitor(&mut self.child);
+    let mut borders = [&mut self.right, &mut self.bottom_left, &mut self.bottom, &mut self.bottom_right];
+    for border in borders.iter_mut().filter_map(|border| border.as_mut()) {
+      visitor(border);
+    }
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    let before = [&self.top_left, &self.top, &self.top_right, &self.left];
+    let after = [&self.right, &self.bottom_left, &self.bottom, &self.bottom_right];
+    before
+      .iter()
+      .copied()
+      .flatten()
+      .map(|border| border as &dyn Widget)
+      .chain(Some(&self.child as &dyn Widget))
+      .chain(after.iter().copied().flatten().map(|border| border as &dyn Widget))
+      .nth(idx)
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    let before = [&mut self.top_left, &mut self.top, &mut self.top_right, &mut self.left];
+    let after = [&mut self.right, &mut self.bottom_left, &mut self.bottom, &mut self.bottom_right];
+    IntoIterator::into_iter(before)
+      .flatten()
+      .map(|border| border as &mut dyn Widget)
+      .chain(Some(&mut self.child as &mut dyn Widget))
+      .chain(IntoIterator::into_iter(after).flatten().map(|border| border as &mut dyn Widget))
+      .nth(idx)
+  }
 }
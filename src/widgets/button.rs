@@ -1,8 +1,6 @@
-use crate::render::{RenderCtx, Renderer};
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutResult, RenderResult, Widget};
-use crate::FlexFit;
+use crate::render::RenderCtx;
+use crate::widgets::{LayoutResult, RenderResult, Widget};
 use euclid::default::Size2D;
-use std::ops::{Deref, DerefMut};
 
 pub struct Button<Child> {
   child: Child,
@@ -17,21 +15,23 @@ where
   }
 }
 
-impl Widget for Button<&str> {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
+impl<Child> Widget for Button<Child>
+where
+  Child: Widget,
+{
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
-    todo!()
+    self.child.layout(avail_size)
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    ctx.renderer().write(&self.child);
-    Ok(())
+    ctx.render_child_widget(*ctx.get_frame(), &self.child)
+  }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
@@ -1,17 +1,19 @@
 use crate::render::RenderCtx;
 use crate::widgets::{AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderResult, Widget};
-use crate::{Event, MouseEventKind};
+use crate::{Event, KeyCode, MouseEventKind};
+use crossterm::style::{ContentStyle, Stylize};
 use euclid::default::Size2D;
 
 pub struct CheckBox {
   marked: bool,
+  focused: bool,
   marked_str: &'static str,
   unmarked_str: &'static str,
 }
 
 impl CheckBox {
   pub fn new() -> Self {
-    Self { marked: false, marked_str: "◉", unmarked_str: "○" }
+    Self { marked: false, focused: false, marked_str: "◉", unmarked_str: "○" }
   }
 
   pub fn marked(mut self) -> Self {
@@ -23,6 +25,14 @@ impl CheckBox {
     self.marked = false;
     self
   }
+
+  pub fn is_marked(&self) -> bool {
+    self.marked
+  }
+
+  pub fn is_focused(&self) -> bool {
+    self.focused
+  }
 }
 
 impl Widget for CheckBox {
@@ -36,8 +46,24 @@ impl Widget for CheckBox {
           }
           _ => {}
         },
+        Event::Key(key) => match key.code {
+          KeyCode::Char(' ') | KeyCode::Enter => {
+            self.marked = !self.marked;
+            return EventResult::Done;
+          }
+          _ => {}
+        },
         _ => {}
       },
+      AnyEvent::FocusIn => {
+        self.focused = true;
+        return EventResult::Done;
+      }
+      AnyEvent::FocusOut => {
+        self.focused = false;
+        return EventResult::Done;
+      }
+      AnyEvent::Mouse(_) | AnyEvent::FocusWithin(_) | AnyEvent::Tick | AnyEvent::Custom(_) => {}
     }
     EventResult::Unhandled
   }
@@ -53,10 +79,12 @@ impl Widget for CheckBox {
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    if self.marked {
-      ctx.renderer().write(self.marked_str);
+    let mark = if self.marked { self.marked_str } else { self.unmarked_str };
+    if self.focused {
+      let style = ContentStyle::new().reverse();
+      ctx.render_child_widget_styled(ctx.get_frame().clone(), &style, &mark)?;
     } else {
-      ctx.renderer().write(self.unmarked_str);
+      ctx.renderer().write(mark);
     }
 
     // if self.marked {
@@ -79,6 +107,6 @@ impl Widget for CheckBox {
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+    matches!(capability, Capability::Selectable)
   }
 }
@@ -1,7 +1,5 @@
 use crate::render::RenderCtx;
-use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
-};
+use crate::widgets::{LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget};
 use crate::{compute_flex_layout, ChildrenStorage, MinMaxFlex};
 use euclid::default::{Point2D, Rect, Size2D};
 use std::ops::Deref;
@@ -101,10 +99,6 @@ impl<Children> Widget for ColumnView<Children>
 where
   Children: ChildrenStorage,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, total_avail_size: &Size2D<usize>) -> LayoutResult {
     Ok(self.layout_impl(total_avail_size)?.0)
   }
@@ -129,7 +123,19 @@ where
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    self.children.visit(visitor);
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    self.children.visit_mut(visitor);
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    self.children.get_child(idx)
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    self.children.get_child_mut(idx)
   }
 }
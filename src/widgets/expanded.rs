@@ -1,5 +1,5 @@
 use crate::render::RenderCtx;
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutResult, LayoutSize, RenderResult, Widget};
+use crate::widgets::{AnyEvent, EventResult, LayoutResult, LayoutSize, RenderResult, Widget};
 use crate::FlexFit;
 use euclid::default::Size2D;
 
@@ -22,20 +22,20 @@ impl<Child> Widget for Expanded<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    self.child.event(event, size)
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     let layout = self.child.layout(avail_size)?;
     Ok(layout.max(avail_size.clone()).flex(self.flex).fit(FlexFit::Tight))
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    self.child.render(ctx)
+    ctx.render_child_widget(*ctx.get_frame(), &self.child)
+  }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
@@ -1,7 +1,8 @@
 use euclid::default::Size2D;
+use unicode_width::UnicodeWidthChar;
 
 use crate::render::RenderCtx;
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutResult, LayoutSize, RenderResult, Widget};
+use crate::widgets::{LayoutResult, LayoutSize, RenderResult, Widget};
 use crate::FlexFit;
 
 pub struct FillChar {
@@ -15,17 +16,13 @@ impl FillChar {
 }
 
 impl Widget for FillChar {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     Ok(LayoutSize::min_max(avail_size.clone(), avail_size.clone()))
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
     let frame = ctx.get_frame();
-    let str = self.char.to_string().repeat(frame.width());
+    let str = self.char.to_string().repeat(frame.width() / self.char.width().unwrap_or(1).max(1));
     for row in 1..frame.height() {
       ctx.renderer().write(str.as_str());
       ctx.renderer().next_line();
@@ -33,8 +30,4 @@ impl Widget for FillChar {
     ctx.renderer().write(str.as_str());
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
-  }
 }
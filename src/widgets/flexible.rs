@@ -1,7 +1,7 @@
 use euclid::default::Size2D;
 
 use crate::render::RenderCtx;
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutResult, RenderError, RenderResult, Widget};
+use crate::widgets::{AnyEvent, EventResult, LayoutResult, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 
 pub struct Flexible<Child> {
@@ -40,10 +40,6 @@ impl<Child> Widget for Flexible<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    self.child.event(event, size)
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     Ok(self.child.layout(avail_size)?.flex(self.flex).fit(self.fit.clone()))
   }
@@ -52,7 +48,11 @@ where
     ctx.render_child_widget(ctx.get_frame().clone(), &self.child)
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
@@ -18,7 +18,7 @@ where
 {
   pub fn child(child: Child) -> Self {
     Self {
-      on_event: Box::new(|child, event, size| child.event(event, size)),
+      on_event: Box::new(|_, _, _| EventResult::Unhandled),
       on_layout: Box::new(|child, avail_size| child.layout(avail_size)),
       on_render: Box::new(|child, render_ctx| render_ctx.render_child_widget(render_ctx.get_frame().clone(), child)),
       on_has_capability: Box::new(|child, capability| child.has_capability(capability)),
@@ -26,6 +26,9 @@ where
     }
   }
 
+  /// Called with the events routed to the hook itself: the keys and clicks its descendants left unhandled,
+  /// and the ticks and messages sent to every widget. The child gets its own events from the event loop,
+  /// they are not passed to it through the hook, and by default the hook leaves every event unhandled.
   pub fn on_event<F>(mut self, func: F) -> Self
   where
     F: 'static + FnMut(/*child:*/ &mut Child, /*event:*/ &AnyEvent, /*size:*/ &Size2D<usize>) -> EventResult,
@@ -59,7 +62,10 @@ where
   }
 }
 
-impl<Child> Widget for Hook<Child> {
+impl<Child> Widget for Hook<Child>
+where
+  Child: Widget,
+{
   fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
     self.on_event.deref_mut()(&mut self.child, event, size)
   }
@@ -75,4 +81,12 @@ impl<Child> Widget for Hook<Child> {
   fn has_capability(&self, capability: &Capability) -> bool {
     self.on_has_capability.deref()(&self.child, capability)
   }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
+  }
 }
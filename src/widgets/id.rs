@@ -0,0 +1,106 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+use euclid::default::Size2D;
+
+use crate::render::RenderCtx;
+use crate::widgets::{LayoutResult, RenderResult, Widget};
+
+/// String or integer identifying a widget in the tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WidgetId {
+  Name(Cow<'static, str>),
+  Number(i64),
+}
+
+impl From<&'static str> for WidgetId {
+  fn from(name: &'static str) -> Self {
+    WidgetId::Name(Cow::Borrowed(name))
+  }
+}
+
+impl From<String> for WidgetId {
+  fn from(name: String) -> Self {
+    WidgetId::Name(Cow::Owned(name))
+  }
+}
+
+impl From<i32> for WidgetId {
+  fn from(number: i32) -> Self {
+    WidgetId::Number(number as i64)
+  }
+}
+
+impl From<i64> for WidgetId {
+  fn from(number: i64) -> Self {
+    WidgetId::Number(number)
+  }
+}
+
+impl From<usize> for WidgetId {
+  fn from(number: usize) -> Self {
+    WidgetId::Number(number as i64)
+  }
+}
+
+impl Display for WidgetId {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      WidgetId::Name(name) => write!(f, "{}", name),
+      WidgetId::Number(number) => write!(f, "{}", number),
+    }
+  }
+}
+
+/// Gives an ID to its child, to find it again in the tree with `WiTui::find_mut`.
+pub struct Id<Child> {
+  pub id: WidgetId,
+  pub child: Child,
+}
+
+impl Id<()> {
+  pub fn new<I: Into<WidgetId>>(id: I) -> Self {
+    Self {
+      id: id.into(),
+      child: (),
+    }
+  }
+
+  pub fn child<Child: Widget>(self, child: Child) -> Id<Child> {
+    Id { id: self.id, child }
+  }
+}
+
+impl<Child> Widget for Id<Child>
+where
+  Child: Widget + 'static,
+{
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    self.child.layout(avail_size)
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.render_child_widget(*ctx.get_frame(), &self.child)
+  }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
+  }
+
+  fn id(&self) -> Option<WidgetId> {
+    Some(self.id.clone())
+  }
+
+  fn as_any(&self) -> Option<&dyn Any> {
+    Some(&self.child)
+  }
+
+  fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+    Some(&mut self.child)
+  }
+}
@@ -2,7 +2,7 @@ use euclid::default::{Rect, Size2D};
 
 use crate::log::debug;
 use crate::render::RenderCtx;
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutResult, RenderResult, Widget};
+use crate::widgets::{LayoutResult, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 
 // TODO: LeakHorizontal
@@ -23,13 +23,9 @@ impl<Child> Widget for Leak<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     debug!("layout() : avail_size: {:?}", avail_size);
-    let mut layout = self.child.layout(&Size2D::new(1000, 200)).unwrap();
+    let mut layout = self.child.layout(&Size2D::new(1000, 200))?;
     layout.min.width = std::cmp::min(layout.min.width, 1);
     layout.min.height = std::cmp::min(layout.min.height, 1);
     debug!("layout() : layout: {:?}", layout);
@@ -39,14 +35,18 @@ where
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
     let frame = ctx.get_frame().clone();
     debug!("render() : frame: {:?}, ", &frame);
-    let mut layout = self.child.layout(&Size2D::new(1000, 200)).unwrap();
+    let mut layout = self.child.layout(&Size2D::new(1000, 200)).map_err(RenderError::Layout)?;
     layout.min.width = std::cmp::max(layout.min.width, frame.size.width);
     layout.min.height = std::cmp::max(layout.min.height, frame.size.height);
     ctx.render_child_widget(Rect::new(frame.origin.clone(), layout.min.clone()), &self.child)?;
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
@@ -1,7 +1,5 @@
 use crate::render::RenderCtx;
-use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
-};
+use crate::widgets::{LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 use euclid::default::{Rect, Size2D};
 
@@ -39,10 +37,6 @@ impl<Child> Widget for Min<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     if !avail_size.contains(self.min.clone()) {
       return Err(LayoutError::InsufficientSpace);
@@ -89,7 +83,11 @@ where
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
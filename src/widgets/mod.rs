@@ -1,15 +1,19 @@
 use crate::log::debug;
 use crate::render::{RenderCtx, Renderer};
-use crate::util::Scoped;
+use crate::util::{display_width, truncate, Scoped};
 use crate::FlexFit;
+use crossterm::event::MouseButton;
 use crossterm::style::StyledContent;
-use euclid::default::Size2D;
+use euclid::default::{Point2D, Size2D, Vector2D};
+use std::any::Any;
 use std::borrow::Cow;
 use std::cell::{Cell, RefCell};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
+use std::time::Duration;
+use unicode_width::UnicodeWidthChar;
 
 //
 // Export std Widgets
@@ -23,18 +27,25 @@ pub use expanded::Expanded;
 pub use fillchar::FillChar;
 pub use flexible::Flexible;
 pub use hook::Hook;
+pub use id::{Id, WidgetId};
 pub use leak::Leak;
 pub use min::Min;
 pub use padding::Padding;
+pub use progressbar::ProgressBar;
 pub use repeat::Repeat;
 pub use rowview::RowView;
+pub use scrollbar::{ScrollRequest, Scrollbar, Scrolled};
+pub use scrollview::ScrollView;
 pub use stack::Stack;
 pub use styled::Styled;
 #[doc(inline)]
 pub use table::Table;
+pub use tabs::Tabs;
 #[doc(inline)]
 pub use text::Text;
 pub use textinput::TextInput;
+#[cfg(feature = "derive")]
+pub use witui_derive::Widget;
 
 mod align;
 mod borders;
@@ -46,6 +57,7 @@ mod fillchar;
 mod flex;
 mod flexible;
 mod hook;
+mod id;
 mod leak;
 mod min;
 mod padding;
@@ -53,6 +65,7 @@ mod progressbar;
 mod repeat;
 mod rowview;
 mod scrollbar;
+mod scrollview;
 mod stack;
 mod styled;
 pub mod table;
@@ -84,6 +97,8 @@ impl Error for LayoutError {}
 #[derive(Debug)]
 pub enum RenderError {
   Layout(LayoutError),
+  /// The terminal could not be set up for rendering.
+  Io(std::io::Error),
 }
 
 impl Display for RenderError {
@@ -92,6 +107,9 @@ impl Display for RenderError {
       RenderError::Layout(err) => {
         write!(f, "Render error -> {}", err)
       }
+      RenderError::Io(err) => {
+        write!(f, "Render error -> {}", err)
+      }
     }
   }
 }
@@ -156,17 +174,88 @@ pub enum EventResult {
   Done,
   LockMouseClick,
   PopupMenu { options: Vec<Box<dyn Widget>> },
+  /// Message emitted for the `App` run by the WiTui, handed to its update.
+  Message(Box<dyn Any>),
+}
+
+impl EventResult {
+  pub fn message<M: Any>(msg: M) -> Self {
+    EventResult::Message(Box::new(msg))
+  }
 }
 
 pub enum AnyEvent {
   Input(crossterm::event::Event),
+  /// The widget became the target of the keyboard input.
+  FocusIn,
+  /// The widget is no longer the target of the keyboard input.
+  FocusOut,
+  /// A descendant became the target of the keyboard input, at the path of child indexes from this widget down to it.
+  /// Sent to each ancestor of the focused widget after its `FocusIn`, innermost first.
+  FocusWithin(Vec<usize>),
+  /// Gesture made out of the raw mouse input, delivered after the raw event that completed it.
+  Mouse(MouseGesture),
+  /// Sent to every widget at the tick rate of the WiTui.
+  Tick,
+  /// Message sent to every widget through a `Handle`, to be downcast by the widgets expecting it.
+  Custom(Box<dyn Any + Send>),
 }
 
+/// Higher-level mouse events, positions are relative to the receiving widget's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseGesture {
+  /// A button was pressed and released over the same widget.
+  Click { button: MouseButton, position: Point2D<usize> },
+  /// Second click of a button on the same cell within `DOUBLE_CLICK_INTERVAL`, it follows the second `Click`.
+  DoubleClick { button: MouseButton, position: Point2D<usize> },
+  /// The pointer moved with a button held down, origin is where the button was pressed.
+  /// The widget handling `DragStart` gets the `DragMove`s and the `DragEnd` that follow,
+  /// wherever the pointer goes; offset is the pointer distance from the origin.
+  DragStart { button: MouseButton, origin: Point2D<usize> },
+  DragMove { button: MouseButton, origin: Point2D<usize>, offset: Vector2D<isize> },
+  DragEnd { button: MouseButton, origin: Point2D<usize>, offset: Vector2D<isize> },
+  /// The pointer entered the widget's frame.
+  HoverEnter,
+  /// The pointer left the widget's frame.
+  HoverLeave,
+  /// The scroll wheel turned by delta lines, positive is down.
+  Wheel { delta: isize, position: Point2D<usize> },
+}
+
+/// Longest time between two clicks for them to make a double-click.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 pub enum Capability {
+  /// Takes the keyboard focus, only the innermost selectable widgets join the focus chain.
   Selectable,
   Custom(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+  Horizontal,
+  Vertical,
+}
+
+/// Where a viewport is over a larger content along one direction, in cells.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScrollPosition {
+  pub content: usize,
+  pub viewport: usize,
+  pub offset: usize,
+}
+
+impl ScrollPosition {
+  pub fn new(content: usize, viewport: usize, offset: usize) -> Self {
+    Self { content, viewport, offset }
+  }
+
+  /// Largest offset, for the viewport to end with the content.
+  pub fn max_offset(&self) -> usize {
+    self.content.saturating_sub(self.viewport)
+  }
+}
+
 pub trait BoxWidget: Widget + 'static {
   fn box_widget(self) -> Box<dyn Widget>;
 }
@@ -181,23 +270,89 @@ where
 }
 
 pub trait Widget {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult;
+  /// Handle an event, the ones left unhandled go on to the parent.
+  fn event(&mut self, _event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    EventResult::Unhandled
+  }
+
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult;
   fn render(&self, ctx: &RenderCtx) -> RenderResult;
-  fn has_capability(&self, capability: &Capability) -> bool;
+
+  /// The child's capabilities for a single-child wrapper, none otherwise.
+  fn has_capability(&self, capability: &Capability) -> bool {
+    self.single_child().is_some_and(|child| child.has_capability(capability))
+  }
+
+  /// The only child of a wrapper, which the default `has_capability`, `visit_children` and `get_child`
+  /// forward to. Wrappers only implement it, along with `layout` and `render`.
+  fn single_child(&self) -> Option<&dyn Widget> {
+    None
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    None
+  }
+
+  /// Call the visitor on each direct child, in the order they are laid out.
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    if let Some(child) = self.single_child() {
+      visitor(child);
+    }
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    if let Some(child) = self.single_child_mut() {
+      visitor(child);
+    }
+  }
+
+  /// The child at the index in the order of `visit_children`, if the widget keeps it rather than building it
+  /// on the fly. Lets `WiTui::find` lend the widgets of the tree.
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    match idx {
+      0 => self.single_child(),
+      _ => None,
+    }
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    match idx {
+      0 => self.single_child_mut(),
+      _ => None,
+    }
+  }
+
+  /// Where the viewport is over the content along the orientation, for the widgets scrolling their content,
+  /// given the size of their frame.
+  fn scroll_position(&self, _orientation: Orientation, _size: &Size2D<usize>) -> Option<ScrollPosition> {
+    None
+  }
+
+  /// Scroll the content to the offset along the orientation, for the widgets having a scroll position.
+  fn scroll_to(&mut self, _orientation: Orientation, _offset: usize, _size: &Size2D<usize>) {}
+
+  /// ID given to the widget with an `Id`.
+  fn id(&self) -> Option<WidgetId> {
+    None
+  }
+
+  /// The identified widget for downcasting, only an `Id` provides it.
+  fn as_any(&self) -> Option<&dyn Any> {
+    None
+  }
+
+  fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+    None
+  }
 }
 
 // TODO: Default impl of Widgets
 // impl Widget for Fn {}
 
 impl Widget for &str {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     let min = Size2D::new(1, 1);
-    let mut max = Size2D::new(self.chars().count(), 1);
+    let mut max = Size2D::new(display_width(self), 1);
     // clamp max size to parent size
     // max.width = std::cmp::min(max.width, avail_size.width);
     // max.height = std::cmp::min(max.height, avail_size.height);
@@ -211,32 +366,21 @@ impl Widget for &str {
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
     let frame = ctx.get_frame().size.clone();
-    if frame.width < self.chars().count() {
-      let buf = self
-        .chars()
-        .take(frame.width.checked_sub(1).unwrap_or(0))
-        .collect::<String>();
-      ctx.renderer().write(buf.as_str());
+    if frame.width < display_width(self) {
+      let (buf, _) = truncate(self, frame.width.checked_sub(1).unwrap_or(0));
+      ctx.renderer().write(buf);
       ctx.renderer().write("…");
     } else {
       ctx.renderer().write(self);
     }
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    false
-  }
 }
 
 impl Widget for String {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     let min = Size2D::new(1, 1);
-    let mut max = Size2D::new(self.chars().count(), 1);
+    let mut max = Size2D::new(display_width(self), 1);
     // clamp max size to parent size
     // max.width = std::cmp::min(max.width, avail_size.width);
     // max.height = std::cmp::min(max.height, avail_size.height);
@@ -250,31 +394,20 @@ impl Widget for String {
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
     let frame = ctx.get_frame().size.clone();
-    if frame.width < self.chars().count() {
-      let buf = self
-        .chars()
-        .take(frame.width.checked_sub(1).unwrap_or(0))
-        .collect::<String>();
-      ctx.renderer().write(buf.as_str());
+    if frame.width < display_width(self) {
+      let (buf, _) = truncate(self, frame.width.checked_sub(1).unwrap_or(0));
+      ctx.renderer().write(buf);
       ctx.renderer().write("…");
     } else {
       ctx.renderer().write(self);
     }
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    false
-  }
 }
 
 impl Widget for char {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
-    let size = Size2D::new(1, 1);
+    let size = Size2D::new(self.width().unwrap_or(0).max(1), 1);
     // check for minimum space in parent size
     if avail_size.contains(size.clone()) {
       Ok(LayoutSize::min_max(size.clone(), size))
@@ -287,17 +420,9 @@ impl Widget for char {
     ctx.renderer().write(self.to_string().as_str());
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    false
-  }
 }
 
 impl Widget for u32 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     let value = format!("{}", self);
     let min = Size2D::new(1, 1);
@@ -325,17 +450,9 @@ impl Widget for u32 {
     }
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    false
-  }
 }
 
 impl Widget for usize {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     let value = format!("{}", self);
     let min = Size2D::new(1, 1);
@@ -363,10 +480,6 @@ impl Widget for usize {
     }
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    false
-  }
 }
 
 impl<T> Widget for Rc<T>
@@ -392,6 +505,46 @@ where
   fn has_capability(&self, capability: &Capability) -> bool {
     self.deref().has_capability(capability)
   }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    self.deref().visit_children(visitor)
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    if let Some(inner) = Rc::get_mut(self) {
+      inner.visit_children_mut(visitor)
+    }
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    self.deref().get_child(idx)
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    Rc::get_mut(self).and_then(|inner| inner.get_child_mut(idx))
+  }
+
+  fn scroll_position(&self, orientation: Orientation, size: &Size2D<usize>) -> Option<ScrollPosition> {
+    self.deref().scroll_position(orientation, size)
+  }
+
+  fn scroll_to(&mut self, orientation: Orientation, offset: usize, size: &Size2D<usize>) {
+    if let Some(inner) = Rc::get_mut(self) {
+      inner.scroll_to(orientation, offset, size)
+    }
+  }
+
+  fn id(&self) -> Option<WidgetId> {
+    self.deref().id()
+  }
+
+  fn as_any(&self) -> Option<&dyn Any> {
+    self.deref().as_any()
+  }
+
+  fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+    Rc::get_mut(self).and_then(|inner| inner.as_any_mut())
+  }
 }
 
 impl<T> Widget for RefCell<T>
@@ -403,15 +556,49 @@ where
   }
 
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
-    self.deref().layout(avail_size)
+    self.borrow().layout(avail_size)
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    self.deref().render(ctx)
+    self.borrow().render(ctx)
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
-    self.deref().has_capability(capability)
+    self.borrow().has_capability(capability)
+  }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    self.borrow().visit_children(visitor)
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    self.get_mut().visit_children_mut(visitor)
+  }
+
+  /// The children are behind the borrow, only lent mutably.
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    self.get_mut().get_child_mut(idx)
+  }
+
+  fn scroll_position(&self, orientation: Orientation, size: &Size2D<usize>) -> Option<ScrollPosition> {
+    self.borrow().scroll_position(orientation, size)
+  }
+
+  fn scroll_to(&mut self, orientation: Orientation, offset: usize, size: &Size2D<usize>) {
+    self.get_mut().scroll_to(orientation, offset, size)
+  }
+
+  fn id(&self) -> Option<WidgetId> {
+    self.borrow().id()
+  }
+
+  /// None, the widget cannot be lent past the borrow of the cell.
+  fn as_any(&self) -> Option<&dyn Any> {
+    None
+  }
+
+  fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+    self.get_mut().as_any_mut()
   }
 }
 
@@ -435,13 +622,45 @@ where
   fn has_capability(&self, capability: &Capability) -> bool {
     self.deref().has_capability(capability)
   }
-}
 
-impl Widget for () {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    EventResult::Unhandled
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    self.deref().visit_children(visitor)
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    self.deref_mut().visit_children_mut(visitor)
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    self.deref().get_child(idx)
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    self.deref_mut().get_child_mut(idx)
+  }
+
+  fn scroll_position(&self, orientation: Orientation, size: &Size2D<usize>) -> Option<ScrollPosition> {
+    self.deref().scroll_position(orientation, size)
+  }
+
+  fn scroll_to(&mut self, orientation: Orientation, offset: usize, size: &Size2D<usize>) {
+    self.deref_mut().scroll_to(orientation, offset, size)
+  }
+
+  fn id(&self) -> Option<WidgetId> {
+    self.deref().id()
+  }
+
+  fn as_any(&self) -> Option<&dyn Any> {
+    self.deref().as_any()
   }
 
+  fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+    self.deref_mut().as_any_mut()
+  }
+}
+
+impl Widget for () {
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     debug!("layout() : avail_size: {:?}", avail_size);
     let layout = LayoutSize::min_max(Default::default(), Default::default());
@@ -453,35 +672,18 @@ impl Widget for () {
     debug!("render() : frame: {:?}, ", &ctx.get_frame());
     Ok(())
   }
-
-  fn has_capability(&self, capability: &Capability) -> bool {
-    false
-  }
 }
 
 impl<D> Widget for StyledContent<D>
 where
   D: Widget + std::fmt::Display,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    EventResult::Unhandled
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     self.content().layout(avail_size)
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    if !self.style().attributes.is_empty() {
-      ctx.renderer().add_attributes(self.style().attributes);
-    }
-    if let Some(bg) = self.style().background_color.as_ref() {
-      ctx.renderer().set_background(bg);
-    }
-    if let Some(fg) = self.style().foreground_color.as_ref() {
-      ctx.renderer().set_foreground(fg);
-    }
-    self.content().render(ctx)
+    ctx.render_child_widget_styled(ctx.get_frame().clone(), self.style(), self.content())
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
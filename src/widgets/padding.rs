@@ -1,7 +1,5 @@
 use crate::render::RenderCtx;
-use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
-};
+use crate::widgets::{AnyEvent, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 use euclid::default::{Point2D, Rect, SideOffsets2D, Size2D};
 
@@ -62,10 +60,6 @@ impl<Child> Widget for Padding<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    self.child.event(event, size)
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     if avail_size.width < self.offsets.left + self.offsets.right
       || avail_size.height < self.offsets.top + self.offsets.bottom
@@ -93,7 +87,11 @@ where
     ctx.render_child_widget(child_frame, &self.child)
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
@@ -1 +1,253 @@
+use crossterm::style::{Attribute, ContentStyle};
+use euclid::default::{Point2D, Rect, Size2D};
+use unicode_segmentation::UnicodeSegmentation;
 
+use crate::render::RenderCtx;
+use crate::util::{coverage, covered, display_width, grapheme_width, Coverage, LEFT_EIGHTHS, LOWER_EIGHTHS};
+use crate::widgets::{
+  AnyEvent, EventResult, FillChar, LayoutError, LayoutResult, LayoutSize, Orientation, RenderResult, Widget,
+};
+use crate::Style;
+
+/// Length of the bar when it is not stretched, by an `Expanded` for instance.
+const DEFAULT_LENGTH: usize = 20;
+/// Eighths of a cell the indeterminate bar moves by at each tick.
+const STEP: usize = 4;
+
+/// Bar filled up to the ratio of a value to its maximum, to the eighth of a cell.
+/// Horizontal bars fill from the left, vertical ones from the bottom. The label is centered over the bar,
+/// its colors inverted where the bar passes under it.
+/// An indeterminate bar has a segment bouncing from one end to the other, moving at each tick.
+/// It makes gauges as well, a gauge being a bar labeled with its percentage, see `ProgressBar::gauge`.
+pub struct ProgressBar {
+  pub orientation: Orientation,
+  pub value: f64,
+  pub max: f64,
+  pub indeterminate: bool,
+  pub label: Option<String>,
+  /// Label the bar with its percentage when it has no label.
+  pub percentage: bool,
+  pub fill: char,
+  pub empty: char,
+  pub bar_style: Style,
+  pub track_style: Style,
+  /// Ticks since the indeterminate bar started.
+  phase: usize,
+}
+
+impl ProgressBar {
+  pub fn new() -> Self {
+    Self {
+      orientation: Orientation::Horizontal,
+      value: 0.0,
+      max: 1.0,
+      indeterminate: false,
+      label: None,
+      percentage: false,
+      fill: '█',
+      empty: ' ',
+      bar_style: Style::new(),
+      track_style: Style::new(),
+      phase: 0,
+    }
+  }
+
+  /// Bar at the given ratio, labeled with its percentage.
+  pub fn gauge(ratio: f64) -> Self {
+    Self::new().ratio(ratio).percentage()
+  }
+
+  pub fn vertical(mut self) -> Self {
+    self.orientation = Orientation::Vertical;
+    self
+  }
+
+  pub fn horizontal(mut self) -> Self {
+    self.orientation = Orientation::Horizontal;
+    self
+  }
+
+  pub fn ratio(self, ratio: f64) -> Self {
+    self.value(ratio, 1.0)
+  }
+
+  pub fn value(mut self, value: f64, max: f64) -> Self {
+    self.value = value;
+    self.max = max;
+    self.indeterminate = false;
+    self
+  }
+
+  pub fn indeterminate(mut self) -> Self {
+    self.indeterminate = true;
+    self
+  }
+
+  pub fn label<L: Into<String>>(mut self, label: L) -> Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  pub fn percentage(mut self) -> Self {
+    self.percentage = true;
+    self
+  }
+
+  pub fn fill(mut self, fill: char) -> Self {
+    self.fill = fill;
+    self
+  }
+
+  pub fn empty(mut self, empty: char) -> Self {
+    self.empty = empty;
+    self
+  }
+
+  pub fn bar_style(mut self, style: Style) -> Self {
+    self.bar_style = style;
+    self
+  }
+
+  pub fn track_style(mut self, style: Style) -> Self {
+    self.track_style = style;
+    self
+  }
+
+  /// Ratio of the value to the maximum, from 0 to 1.
+  pub fn get_ratio(&self) -> f64 {
+    if self.max > 0.0 {
+      (self.value / self.max).clamp(0.0, 1.0)
+    } else {
+      0.0
+    }
+  }
+
+  fn label_text(&self) -> Option<String> {
+    match (&self.label, self.percentage && !self.indeterminate) {
+      (Some(label), _) => Some(label.clone()),
+      (None, true) => Some(format!("{}%", (self.get_ratio() * 100.0).round())),
+      (None, false) => None,
+    }
+  }
+
+  /// Part of a bar of the given length that is filled, in eighths of a cell.
+  fn span(&self, length: usize) -> (usize, usize) {
+    let total = length * 8;
+    if !self.indeterminate {
+      return (0, (self.get_ratio() * total as f64).round() as usize);
+    }
+    let size = (total / 4).max(8).min(total);
+    let travel = total - size;
+    if travel == 0 {
+      return (0, total);
+    }
+    let at = self.phase * STEP % (2 * travel);
+    let start = if at <= travel { at } else { 2 * travel - at };
+    (start, start + size)
+  }
+
+  /// Cell at the index along the bar, counted from where the bar fills from, within the frame.
+  fn cell_rect(&self, frame: &Rect<usize>, idx: usize) -> Rect<usize> {
+    match self.orientation {
+      Orientation::Horizontal => Rect::new(
+        Point2D::new(frame.min_x() + idx, frame.min_y()),
+        Size2D::new(1, frame.height()),
+      ),
+      Orientation::Vertical => Rect::new(
+        Point2D::new(frame.min_x(), frame.max_y() - 1 - idx),
+        Size2D::new(frame.width(), 1),
+      ),
+    }
+  }
+}
+
+impl Default for ProgressBar {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Widget for ProgressBar {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Tick if self.indeterminate => {
+        self.phase = self.phase.wrapping_add(1);
+        EventResult::Done
+      }
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let label = self.label_text().map_or(0, |label| display_width(&label));
+    let min = Size2D::new(label.max(1), 1);
+    let max = match self.orientation {
+      Orientation::Horizontal => Size2D::new(DEFAULT_LENGTH.max(label), 1),
+      Orientation::Vertical => Size2D::new(label.max(1), DEFAULT_LENGTH),
+    };
+    if !avail_size.contains(min) {
+      return Err(LayoutError::InsufficientSpace);
+    }
+    Ok(LayoutSize::min_max(min, max.min(*avail_size)))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = *ctx.get_frame();
+    let (length, blocks) = match self.orientation {
+      Orientation::Horizontal => (frame.width(), LEFT_EIGHTHS),
+      Orientation::Vertical => (frame.height(), LOWER_EIGHTHS),
+    };
+    let span = self.span(length);
+    let bar = ContentStyle::from(&self.bar_style);
+    for idx in 0..length {
+      let (glyph, style) = match coverage(idx, span, true) {
+        Coverage::Empty => (self.empty, ContentStyle::from(&self.track_style)),
+        Coverage::Full => (self.fill, bar),
+        Coverage::Partial { eighths, reversed } => {
+          let mut style = ContentStyle {
+            background_color: self.track_style.bg,
+            ..bar
+          };
+          if reversed {
+            style.attributes = style.attributes | Attribute::Reverse;
+          }
+          (blocks[eighths - 1], style)
+        }
+      };
+      ctx.render_child_widget_styled(self.cell_rect(&frame, idx), &style, &FillChar::new(glyph))?;
+    }
+
+    let label = match self.label_text() {
+      Some(label) => label,
+      None => return Ok(()),
+    };
+    let mut x = frame.min_x() + frame.width().saturating_sub(display_width(&label)) / 2;
+    let y = frame.min_y() + frame.height().saturating_sub(1) / 2;
+    for grapheme in label.graphemes(true) {
+      let width = grapheme_width(grapheme);
+      if width == 0 {
+        continue;
+      }
+      if x + width > frame.max_x() {
+        break;
+      }
+      let idx = match self.orientation {
+        Orientation::Horizontal => x - frame.min_x(),
+        Orientation::Vertical => frame.max_y() - 1 - y,
+      };
+      // inverted where at least half of the cell is filled
+      let style = if covered(idx, span) >= 4 {
+        ContentStyle {
+          attributes: bar.attributes | Attribute::Reverse,
+          ..bar
+        }
+      } else {
+        ContentStyle::from(&self.track_style)
+      };
+      let cell = Rect::new(Point2D::new(x, y), Size2D::new(width, 1));
+      ctx.render_child_widget_styled(cell, &style, &grapheme)?;
+      x += width;
+    }
+    Ok(())
+  }
+}
@@ -3,7 +3,7 @@ use euclid::default::{Point2D, Rect, Size2D};
 use euclid::SideOffsets2D;
 
 use crate::render::RenderCtx;
-use crate::widgets::{AnyEvent, Capability, EventResult, LayoutError, LayoutResult, RenderError, RenderResult, Widget};
+use crate::widgets::{LayoutError, LayoutResult, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 
 // TODO: only vertical
@@ -23,10 +23,6 @@ impl<Child> Widget for Repeat<Child>
 where
   Child: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    self.child.event(event, size)
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     let mut layout = self.child.layout(avail_size)?;
     layout.max.width = layout.max.width.max(avail_size.width);
@@ -90,7 +86,11 @@ where
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    self.child.has_capability(capability)
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
   }
 }
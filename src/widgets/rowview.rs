@@ -6,9 +6,7 @@ use euclid::default::{Point2D, Rect, Size2D};
 use crate::log::info;
 use crate::render::RenderCtx;
 use crate::util::Scoped;
-use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
-};
+use crate::widgets::{LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget};
 use crate::FlexFit;
 use crate::{compute_flex_layout, ChildrenStorage, MinMaxFlex};
 use crossterm::style::ContentStyle;
@@ -112,10 +110,6 @@ impl<Children> Widget for RowView<Children>
 where
   Children: ChildrenStorage,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, total_avail_size: &Size2D<usize>) -> LayoutResult {
     Ok(self.layout_impl(total_avail_size)?.0)
   }
@@ -140,7 +134,19 @@ where
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    self.children.visit(visitor);
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    self.children.visit_mut(visitor);
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    self.children.get_child(idx)
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    self.children.get_child_mut(idx)
   }
 }
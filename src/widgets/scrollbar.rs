@@ -1 +1,386 @@
+use crossterm::event::{MouseButton, MouseEvent};
+use crossterm::style::{Attribute, ContentStyle};
+use euclid::default::{Point2D, Rect, Size2D, Vector2D};
 
+use crate::render::RenderCtx;
+use crate::tree;
+use crate::util::{coverage, Coverage, LEFT_EIGHTHS, LOWER_EIGHTHS};
+use crate::widgets::{
+  AnyEvent, EventResult, FillChar, LayoutError, LayoutResult, LayoutSize, MouseGesture, Orientation, RenderResult,
+  ScrollPosition, Widget,
+};
+use crate::{Event, MouseEventKind, Style};
+
+/// Emitted by a `Scrollbar` clicked or dragged, for the content to be scrolled to the offset.
+/// Attached scrollbars scroll the widget they are attached to instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollRequest {
+  pub orientation: Orientation,
+  pub offset: usize,
+}
+
+/// Bar showing where a viewport is over a larger content, the thumb being sized and placed
+/// to the eighth of a cell. Clicking the track pages towards the click, dragging the thumb follows the pointer.
+#[derive(Clone)]
+pub struct Scrollbar {
+  pub orientation: Orientation,
+  pub position: ScrollPosition,
+  pub track: char,
+  pub thumb: char,
+  pub track_style: Style,
+  pub thumb_style: Style,
+  /// Offset when the thumb started being dragged.
+  drag: Option<usize>,
+}
+
+impl Scrollbar {
+  pub fn vertical() -> Self {
+    Self::new(Orientation::Vertical, '│')
+  }
+
+  pub fn horizontal() -> Self {
+    Self::new(Orientation::Horizontal, '─')
+  }
+
+  fn new(orientation: Orientation, track: char) -> Self {
+    Self {
+      orientation,
+      position: ScrollPosition::default(),
+      track,
+      thumb: '█',
+      track_style: Style::new(),
+      thumb_style: Style::new(),
+      drag: None,
+    }
+  }
+
+  pub fn position(mut self, content: usize, viewport: usize, offset: usize) -> Self {
+    self.position = ScrollPosition::new(content, viewport, offset);
+    self
+  }
+
+  pub fn track(mut self, track: char) -> Self {
+    self.track = track;
+    self
+  }
+
+  pub fn thumb(mut self, thumb: char) -> Self {
+    self.thumb = thumb;
+    self
+  }
+
+  pub fn track_style(mut self, style: Style) -> Self {
+    self.track_style = style;
+    self
+  }
+
+  pub fn thumb_style(mut self, style: Style) -> Self {
+    self.thumb_style = style;
+    self
+  }
+
+  /// Attach the scrollbar to a scrollable child, on its right side if vertical, or its bottom side.
+  pub fn attach<Child: Widget>(self, child: Child) -> Scrolled<Child> {
+    Scrolled {
+      vertical: None,
+      horizontal: None,
+      child,
+    }
+    .scrollbar(self)
+  }
+
+  fn length(&self, size: &Size2D<usize>) -> usize {
+    match self.orientation {
+      Orientation::Vertical => size.height,
+      Orientation::Horizontal => size.width,
+    }
+  }
+
+  fn along(&self, point: &Point2D<usize>) -> usize {
+    match self.orientation {
+      Orientation::Vertical => point.y,
+      Orientation::Horizontal => point.x,
+    }
+  }
+
+  /// Start and end of the thumb on a track of the given length, in eighths of a cell.
+  /// The thumb takes the whole track when all the content fits in the viewport.
+  fn thumb_span(&self, length: usize) -> (usize, usize) {
+    let total = length * 8;
+    let position = &self.position;
+    if position.content <= position.viewport {
+      return (0, total);
+    }
+    let size = ((position.viewport * total + position.content / 2) / position.content).clamp(8.min(total), total);
+    let max_offset = position.max_offset();
+    let start = (position.offset.min(max_offset) * (total - size) + max_offset / 2) / max_offset;
+    (start, start + size)
+  }
+
+  /// Glyph and style of the cell at the index along the track.
+  fn cell(&self, idx: usize, span: (usize, usize)) -> (char, ContentStyle) {
+    let (blocks, blocks_at_head) = match self.orientation {
+      Orientation::Vertical => (LOWER_EIGHTHS, false),
+      Orientation::Horizontal => (LEFT_EIGHTHS, true),
+    };
+    let mut style = ContentStyle::from(&self.thumb_style);
+    match coverage(idx, span, blocks_at_head) {
+      Coverage::Empty => (self.track, ContentStyle::from(&self.track_style)),
+      Coverage::Full => (self.thumb, style),
+      Coverage::Partial { eighths, reversed } => {
+        // the track shows through the reversed blocks
+        style.background_color = self.track_style.bg;
+        if reversed {
+          style.attributes = style.attributes | Attribute::Reverse;
+        }
+        (blocks[eighths - 1], style)
+      }
+    }
+  }
+
+  fn request(&self, offset: isize) -> EventResult {
+    let offset = offset.clamp(0, self.position.max_offset() as isize) as usize;
+    EventResult::message(ScrollRequest {
+      orientation: self.orientation,
+      offset,
+    })
+  }
+}
+
+impl Widget for Scrollbar {
+  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
+    let length = self.length(size);
+    let (start, end) = self.thumb_span(length);
+    let on_thumb = |along: usize| along >= start / 8 && along < end.div_ceil(8);
+    let offset = self.position.offset as isize;
+    match event {
+      AnyEvent::Input(Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        ..
+      })) => {
+        let along = self.along(&Point2D::new(*column as usize, *row as usize));
+        if on_thumb(along) {
+          return EventResult::Done;
+        }
+        let page = self.position.viewport.max(1) as isize;
+        self.request(if along < start / 8 { offset - page } else { offset + page })
+      }
+      AnyEvent::Mouse(MouseGesture::DragStart {
+        button: MouseButton::Left,
+        origin,
+      }) if on_thumb(self.along(origin)) => {
+        self.drag = Some(self.position.offset);
+        EventResult::Done
+      }
+      AnyEvent::Mouse(MouseGesture::DragMove { offset: moved, .. })
+      | AnyEvent::Mouse(MouseGesture::DragEnd { offset: moved, .. }) => {
+        let from = match self.drag {
+          Some(from) => from as isize,
+          None => return EventResult::Unhandled,
+        };
+        if let AnyEvent::Mouse(MouseGesture::DragEnd { .. }) = event {
+          self.drag = None;
+        }
+        let moved = match self.orientation {
+          Orientation::Vertical => moved.y,
+          Orientation::Horizontal => moved.x,
+        };
+        // the thumb travels the track but its own length over the whole offset range
+        let travel = (length * 8 - (end - start)) as isize;
+        let delta = if travel > 0 { moved * 8 * self.position.max_offset() as isize / travel } else { 0 };
+        self.request(from + delta)
+      }
+      AnyEvent::Mouse(MouseGesture::Wheel { delta, .. }) => self.request(offset + delta),
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let min = Size2D::new(1, 1);
+    if !avail_size.contains(min) {
+      return Err(LayoutError::InsufficientSpace);
+    }
+    let max = match self.orientation {
+      Orientation::Vertical => Size2D::new(1, avail_size.height),
+      Orientation::Horizontal => Size2D::new(avail_size.width, 1),
+    };
+    Ok(LayoutSize::min_max(min, max))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = *ctx.get_frame();
+    let span = self.thumb_span(self.length(&frame.size));
+    for idx in 0..self.length(&frame.size) {
+      let (glyph, style) = self.cell(idx, span);
+      let cell = match self.orientation {
+        Orientation::Vertical => Rect::new(frame.origin + Vector2D::new(0, idx), Size2D::new(frame.width(), 1)),
+        Orientation::Horizontal => Rect::new(frame.origin + Vector2D::new(idx, 0), Size2D::new(1, frame.height())),
+      };
+      ctx.render_child_widget_styled(cell, &style, &FillChar::new(glyph))?;
+    }
+    Ok(())
+  }
+}
+
+/// Render the scrollbar into the frame, at the scroll position of the widget it is attached to,
+/// given the widget's frame size.
+pub(crate) fn render_attached(
+  bar: &Scrollbar, target: &dyn Widget, viewport: &Size2D<usize>, frame: Rect<usize>, ctx: &RenderCtx,
+) -> RenderResult {
+  let position = target.scroll_position(bar.orientation, viewport).unwrap_or_default();
+  ctx.render_child_widget(frame, &Scrollbar { position, ..bar.clone() })
+}
+
+/// Deliver a mouse event over the scrollbar frame, or a drag it took, to the scrollbar,
+/// then scroll the widget it is attached to as requested. The frame is relative to the widget holding both,
+/// like the event. None if the event is not for the scrollbar.
+pub(crate) fn event_attached(
+  bar: &mut Scrollbar, target: &mut dyn Widget, viewport: &Size2D<usize>, frame: &Rect<usize>, event: &AnyEvent,
+) -> Option<EventResult> {
+  let relative = |point: &Point2D<usize>| tree::relative(point, frame);
+  let event = match event {
+    AnyEvent::Input(Event::Mouse(mouse)) => {
+      let point = Point2D::new(mouse.column as usize, mouse.row as usize);
+      if !frame.contains(point) {
+        return None;
+      }
+      let point = relative(&point);
+      AnyEvent::Input(Event::Mouse(MouseEvent {
+        column: point.x as u16,
+        row: point.y as u16,
+        ..*mouse
+      }))
+    }
+    AnyEvent::Mouse(MouseGesture::DragStart { button, origin }) if frame.contains(*origin) => {
+      AnyEvent::Mouse(MouseGesture::DragStart {
+        button: *button,
+        origin: relative(origin),
+      })
+    }
+    AnyEvent::Mouse(MouseGesture::DragMove { button, origin, offset }) if bar.drag.is_some() => {
+      AnyEvent::Mouse(MouseGesture::DragMove {
+        button: *button,
+        origin: relative(origin),
+        offset: *offset,
+      })
+    }
+    AnyEvent::Mouse(MouseGesture::DragEnd { button, origin, offset }) if bar.drag.is_some() => {
+      AnyEvent::Mouse(MouseGesture::DragEnd {
+        button: *button,
+        origin: relative(origin),
+        offset: *offset,
+      })
+    }
+    AnyEvent::Mouse(MouseGesture::Wheel { delta, position }) if frame.contains(*position) => {
+      AnyEvent::Mouse(MouseGesture::Wheel {
+        delta: *delta,
+        position: relative(position),
+      })
+    }
+    _ => return None,
+  };
+  bar.position = target.scroll_position(bar.orientation, viewport).unwrap_or_default();
+  Some(match bar.event(&event, &frame.size) {
+    EventResult::Message(message) => match message.downcast::<ScrollRequest>() {
+      Ok(request) => {
+        target.scroll_to(request.orientation, request.offset, viewport);
+        EventResult::Done
+      }
+      Err(message) => EventResult::Message(message),
+    },
+    result => result,
+  })
+}
+
+/// Scrollable child with scrollbars attached to its right and bottom sides, following its scroll position.
+pub struct Scrolled<Child> {
+  pub vertical: Option<Scrollbar>,
+  pub horizontal: Option<Scrollbar>,
+  pub child: Child,
+}
+
+impl<Child> Scrolled<Child> {
+  /// Attach another scrollbar, in place of the one of the same orientation.
+  pub fn scrollbar(mut self, bar: Scrollbar) -> Self {
+    match bar.orientation {
+      Orientation::Vertical => self.vertical = Some(bar),
+      Orientation::Horizontal => self.horizontal = Some(bar),
+    }
+    self
+  }
+
+  /// Space taken by the scrollbars.
+  fn bars_size(&self) -> Size2D<usize> {
+    Size2D::new(self.vertical.is_some() as usize, self.horizontal.is_some() as usize)
+  }
+
+  /// Frames of the child, the vertical and the horizontal scrollbars in a frame of the given size.
+  fn frames(&self, size: &Size2D<usize>) -> (Rect<usize>, Rect<usize>, Rect<usize>) {
+    let child = Rect::from_size(*size - self.bars_size().min(*size));
+    let vertical = Rect::new(Point2D::new(child.max_x(), 0), Size2D::new(size.width - child.width(), child.height()));
+    let horizontal = Rect::new(Point2D::new(0, child.max_y()), Size2D::new(child.width(), size.height - child.height()));
+    (child, vertical, horizontal)
+  }
+}
+
+impl<Child> Widget for Scrolled<Child>
+where
+  Child: Widget,
+{
+  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
+    let (child, vertical, horizontal) = self.frames(size);
+    let bars = [(&mut self.vertical, vertical), (&mut self.horizontal, horizontal)];
+    for (bar, frame) in bars {
+      if let Some(bar) = bar.as_mut() {
+        if let Some(result) = event_attached(bar, &mut self.child, &child.size, &frame, event) {
+          return result;
+        }
+      }
+    }
+    EventResult::Unhandled
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let bars = self.bars_size();
+    if !avail_size.contains(bars) {
+      return Err(LayoutError::InsufficientSpace);
+    }
+    let mut layout = self.child.layout(&(*avail_size - bars))?;
+    layout.min += bars;
+    layout.max += bars;
+    Ok(layout)
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = *ctx.get_frame();
+    let (child, vertical, horizontal) = self.frames(&frame.size);
+    let origin = frame.origin.to_vector();
+    ctx.render_child_widget(child.translate(origin), &self.child)?;
+    if let Some(bar) = self.vertical.as_ref() {
+      render_attached(bar, &self.child, &child.size, vertical.translate(origin), ctx)?;
+    }
+    if let Some(bar) = self.horizontal.as_ref() {
+      render_attached(bar, &self.child, &child.size, horizontal.translate(origin), ctx)?;
+    }
+    Ok(())
+  }
+
+  fn scroll_position(&self, orientation: Orientation, size: &Size2D<usize>) -> Option<ScrollPosition> {
+    self.child.scroll_position(orientation, &self.frames(size).0.size)
+  }
+
+  fn scroll_to(&mut self, orientation: Orientation, offset: usize, size: &Size2D<usize>) {
+    let size = self.frames(size).0.size;
+    self.child.scroll_to(orientation, offset, &size)
+  }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
+  }
+}
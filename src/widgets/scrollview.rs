@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+
+use crate::render::RenderCtx;
+use crate::tree::Frames;
+use crate::widgets::{
+  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, MouseGesture, Orientation, RenderError,
+  RenderResult, ScrollPosition, Widget,
+};
+use crate::{Event, KeyCode};
+use euclid::default::{Point2D, Size2D};
+
+/// Space the child is laid out in to get its preferred size.
+const UNBOUNDED: usize = u16::MAX as usize;
+
+/// Viewport over a child laid out at its preferred size, which can be larger than the frame.
+/// The child is rendered off-screen and the window at the offset is drawn, scrolled with the arrow keys,
+/// PageUp/PageDown, Home/End and the mouse wheel, including the keys a focused descendant leaves unhandled.
+/// The viewport follows the focus, moving for a descendant taking it to show.
+pub struct ScrollView<Child> {
+  /// Top-left cell of the child shown, kept within the child by the events and clamped when rendering.
+  pub offset: Point2D<usize>,
+  pub child: Child,
+  /// Frames of the child's widgets on the content as last rendered, by their path from the view.
+  content_frames: RefCell<Frames>,
+}
+
+impl Default for ScrollView<()> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ScrollView<()> {
+  pub fn new() -> Self {
+    Self {
+      offset: Point2D::zero(),
+      child: (),
+      content_frames: RefCell::default(),
+    }
+  }
+
+  pub fn child<C: Widget>(self, child: C) -> ScrollView<C> {
+    ScrollView {
+      offset: self.offset,
+      child,
+      content_frames: RefCell::default(),
+    }
+  }
+}
+
+impl<Child> ScrollView<Child> {
+  pub fn offset(mut self, offset: Point2D<usize>) -> Self {
+    self.offset = offset;
+    self
+  }
+}
+
+impl<Child> ScrollView<Child>
+where
+  Child: Widget,
+{
+  /// Preferred size of the child, or the viewport size along the axes it fills.
+  fn preferred_size(&self, viewport: &Size2D<usize>) -> Result<Size2D<usize>, LayoutError> {
+    let layout = self.child.layout(&Size2D::new(UNBOUNDED, UNBOUNDED))?;
+    let fit = |min: usize, max: usize, viewport: usize| if min.max(max) < UNBOUNDED { max } else { viewport };
+    Ok(Size2D::new(
+      fit(layout.min.width, layout.max.width, viewport.width),
+      fit(layout.min.height, layout.max.height, viewport.height),
+    ))
+  }
+
+  /// Size the child is rendered at, its preferred size, at least the viewport size.
+  fn content_size(&self, viewport: &Size2D<usize>) -> Result<Size2D<usize>, LayoutError> {
+    Ok(self.preferred_size(viewport)?.max(*viewport))
+  }
+
+  /// Offset kept within the child, for the viewport not to go past its right and bottom edges.
+  fn clamp(offset: Point2D<usize>, content: &Size2D<usize>, viewport: &Size2D<usize>) -> Point2D<usize> {
+    Point2D::new(
+      offset.x.min(content.width.saturating_sub(viewport.width)),
+      offset.y.min(content.height.saturating_sub(viewport.height)),
+    )
+  }
+
+  fn scroll_by(&mut self, dx: isize, dy: isize, viewport: &Size2D<usize>) -> EventResult {
+    let content = match self.content_size(viewport) {
+      Ok(content) => content,
+      Err(_) => return EventResult::Unhandled,
+    };
+    let moved = |at: usize, by: isize| (at as isize).saturating_add(by).max(0) as usize;
+    let offset = Point2D::new(moved(self.offset.x, dx), moved(self.offset.y, dy));
+    self.offset = Self::clamp(offset, &content, viewport);
+    EventResult::Done
+  }
+
+  /// Move the viewport the least for the descendant at path to show, where it was last rendered,
+  /// or its top-left corner when it is larger than the viewport.
+  fn reveal(&mut self, path: &[usize], viewport: &Size2D<usize>) -> EventResult {
+    let area = match self.content_frames.borrow().get(path).first() {
+      Some(frame) => frame.frame,
+      None => return EventResult::Unhandled,
+    };
+    let content = match self.content_size(viewport) {
+      Ok(content) => content,
+      Err(_) => return EventResult::Unhandled,
+    };
+    let shown = |at: usize, start: usize, end: usize, length: usize| {
+      if start < at {
+        start
+      } else if end > at + length {
+        end.saturating_sub(length).min(start)
+      } else {
+        at
+      }
+    };
+    let offset = Point2D::new(
+      shown(self.offset.x, area.min_x(), area.max_x(), viewport.width),
+      shown(self.offset.y, area.min_y(), area.max_y(), viewport.height),
+    );
+    self.offset = Self::clamp(offset, &content, viewport);
+    EventResult::Done
+  }
+}
+
+impl<Child> Widget for ScrollView<Child>
+where
+  Child: Widget,
+{
+  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
+    let page = size.height.saturating_sub(1).max(1) as isize;
+    match event {
+      AnyEvent::Input(Event::Key(key)) => match key.code {
+        KeyCode::Up => self.scroll_by(0, -1, size),
+        KeyCode::Down => self.scroll_by(0, 1, size),
+        KeyCode::Left => self.scroll_by(-1, 0, size),
+        KeyCode::Right => self.scroll_by(1, 0, size),
+        KeyCode::PageUp => self.scroll_by(0, -page, size),
+        KeyCode::PageDown => self.scroll_by(0, page, size),
+        KeyCode::Home => self.scroll_by(0, isize::MIN, size),
+        KeyCode::End => self.scroll_by(0, isize::MAX, size),
+        _ => EventResult::Unhandled,
+      },
+      AnyEvent::Mouse(MouseGesture::Wheel { delta, .. }) => self.scroll_by(0, *delta, size),
+      AnyEvent::FocusWithin(path) => self.reveal(path, size),
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let preferred = self.preferred_size(avail_size)?;
+    Ok(LayoutSize::min_max(
+      Size2D::new(1, 1).min(*avail_size),
+      preferred.min(*avail_size),
+    ))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let viewport = ctx.get_frame().size;
+    let content = self.content_size(&viewport).map_err(RenderError::Layout)?;
+    let offset = Self::clamp(self.offset, &content, &viewport);
+    *self.content_frames.borrow_mut() = ctx.render_scrolled(content, offset, &self.child)?;
+    Ok(())
+  }
+
+  fn scroll_position(&self, orientation: Orientation, size: &Size2D<usize>) -> Option<ScrollPosition> {
+    let content = self.content_size(size).ok()?;
+    let offset = Self::clamp(self.offset, &content, size);
+    Some(match orientation {
+      Orientation::Horizontal => ScrollPosition::new(content.width, size.width, offset.x),
+      Orientation::Vertical => ScrollPosition::new(content.height, size.height, offset.y),
+    })
+  }
+
+  fn scroll_to(&mut self, orientation: Orientation, offset: usize, size: &Size2D<usize>) {
+    let mut target = self.offset;
+    match orientation {
+      Orientation::Horizontal => target.x = offset,
+      Orientation::Vertical => target.y = offset,
+    }
+    if let Ok(content) = self.content_size(size) {
+      self.offset = Self::clamp(target, &content, size);
+    }
+  }
+
+  fn has_capability(&self, capability: &Capability) -> bool {
+    match capability {
+      // only takes the focus when there is nothing focusable in the child
+      Capability::Selectable => true,
+      _ => self.child.has_capability(capability),
+    }
+  }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.child)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.child)
+  }
+}
@@ -1,18 +1,11 @@
 use crate::render::RenderCtx;
 use crate::util::Scoped;
-use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
-};
+use crate::widgets::{LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget};
 use crate::{ChildrenStorage, FlexFit};
 use euclid::default::Size2D;
 use std::cmp::max;
 use std::ops::Deref;
 
-// BUG: bg, fg and attrs, are leaking from one child to the next
-//  e.g.: child 1 renders text with fg(green) and attr(bold)
-//        child 2 renders simple text and ends up inheriting the fg and attr from child 1 settings
-//  need to solve with some sort of push/pop of contexts.
-
 pub struct Stack<Children> {
   pub children: Option<Children>,
   pub must_fit_all_children: bool,
@@ -55,12 +48,11 @@ impl<Children> Widget for Stack<Children>
 where
   Children: ChildrenStorage,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
-    let children = self.children.as_ref().unwrap();
+    let children = match &self.children {
+      Some(children) => children,
+      None => return Ok(LayoutSize::default()),
+    };
     let mut layout = LayoutSize::default();
     for idx in 0..children.len() {
       let child = children.child(idx).unwrap();
@@ -84,7 +76,10 @@ where
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
     let frame = ctx.get_frame().clone();
     let layout = self.layout(&frame.size).map_err(|e| RenderError::Layout(e))?;
-    let children = self.children.as_ref().unwrap();
+    let children = match &self.children {
+      Some(children) => children,
+      None => return Ok(()),
+    };
     for idx in 0..children.len() {
       let child = children.child(idx).unwrap();
       let result = ctx.render_child_dyn_widget(frame.clone(), child.deref());
@@ -99,7 +94,23 @@ where
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    if let Some(children) = self.children.as_ref() {
+      children.visit(visitor);
+    }
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    if let Some(children) = self.children.as_mut() {
+      children.visit_mut(visitor);
+    }
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    self.children.as_ref()?.get_child(idx)
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    self.children.as_mut()?.get_child_mut(idx)
   }
 }
@@ -8,7 +8,8 @@ use euclid::default::{Point2D, Rect, Size2D};
 use crate::render::RenderCtx;
 use crate::util::{MinMax, Scoped, ScopedMut};
 use crate::widgets::{
-  AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
+  AnyEvent, Capability, EventResult, FillChar, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult,
+  Widget,
 };
 use crate::FlexFit;
 use crossterm::event::{Event, MouseButton, MouseEventKind};
@@ -181,6 +182,7 @@ where
         }
         Event::Resize(_, _) => EventResult::Unhandled,
       },
+      _ => EventResult::Unhandled,
     }
   }
 
@@ -194,12 +196,34 @@ where
     // ctx
     //   .renderer()
     //   .set_attributes(Attributes::default() | Attribute::Reverse);
-    self.heading.render(ctx)
+    ctx.render_child_widget(*ctx.get_frame(), &self.heading)
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
     self.heading.has_capability(capability)
   }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    visitor(&self.heading);
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    visitor(&mut self.heading);
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    match idx {
+      0 => Some(&self.heading),
+      _ => None,
+    }
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    match idx {
+      0 => Some(&mut self.heading),
+      _ => None,
+    }
+  }
 }
 
 pub trait TableColumn: Widget {
@@ -286,8 +310,9 @@ impl TableColumns for TableColumnsFn {
     (self.fn_column)(idx)
   }
 
+  /// Generated columns are not kept, so there is nothing to mutate.
   fn column_mut(&mut self, idx: usize) -> Option<ScopedMut<dyn TableColumn>> {
-    todo!()
+    None
   }
 
   fn as_any(&self) -> &dyn Any {
@@ -425,21 +450,39 @@ impl<Heading> Widget for Row<Heading>
 where
   Heading: Widget,
 {
-  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    todo!()
-  }
-
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
     self.heading.layout(avail_size)
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    self.heading.render(ctx)
+    ctx.render_child_widget(*ctx.get_frame(), &self.heading)
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
     self.heading.has_capability(capability)
   }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    visitor(&self.heading);
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    visitor(&mut self.heading);
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    match idx {
+      0 => Some(&self.heading),
+      _ => None,
+    }
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    match idx {
+      0 => Some(&mut self.heading),
+      _ => None,
+    }
+  }
 }
 
 pub trait TableRow: Widget {
@@ -521,6 +564,8 @@ impl TableRows for TableRowsFn {
 pub trait TableData {
   fn rows_len(&self) -> usize;
   fn cell(&self, row: usize, col: usize) -> Option<Scoped<dyn Widget>>;
+  /// Same cell as `cell`, for the events to reach it. A cell generated on the fly is returned boxed.
+  fn cell_mut(&mut self, row: usize, col: usize) -> Option<ScopedMut<'_, dyn Widget>>;
   fn as_any(&self) -> &dyn Any;
   fn as_any_mut(&mut self) -> &mut dyn Any;
 }
@@ -539,6 +584,11 @@ where
       .and_then(|vec| vec.get(col).and_then(|cell| Some(Scoped::Ref(cell as &dyn Widget))))
   }
 
+  fn cell_mut(&mut self, row: usize, col: usize) -> Option<ScopedMut<'_, dyn Widget>> {
+    let cell = self.get_mut(row)?.get_mut(col)?;
+    Some(ScopedMut::Ref(cell as &mut dyn Widget))
+  }
+
   fn as_any(&self) -> &dyn Any {
     self
   }
@@ -559,6 +609,8 @@ pub struct TableLayout {
   // TODO: pub hide_row_headings: bool,
   /// Column separator
   pub column_separator: char,
+  /// Number of times the column separator is repeated between two columns
+  pub column_separator_width: usize,
   // /// Row separator
   // TODO: pub row_separator: char,
   /// Rendering must fit all columns or render nothing at all (declare insufficient space)
@@ -574,6 +626,7 @@ impl Default for TableLayout {
     Self {
       show_column_headings: true,
       column_separator: ' ',
+      column_separator_width: 1,
       must_render_fit_all_columns: false,
     }
   }
@@ -590,6 +643,11 @@ impl TableLayout {
     self
   }
 
+  pub fn column_separator_width(mut self, column_separator_width: usize) -> Self {
+    self.column_separator_width = column_separator_width;
+    self
+  }
+
   pub fn must_render_fit_all_columns(mut self, must_render_fit_all_columns: bool) -> Self {
     self.must_render_fit_all_columns = must_render_fit_all_columns;
     self
@@ -599,7 +657,7 @@ impl TableLayout {
 // https://api.flutter.dev/flutter/widgets/Table-class.html
 // TODO: How to merge cells?
 // TODO: How to serialize?
-// TODO: ScrollView widget on table, ref: https://api.flutter.dev/flutter/widgets/Table/defaultColumnWidth.html
+// NOTE: tables larger than their frame go in a ScrollView, ref: https://api.flutter.dev/flutter/widgets/Table/defaultColumnWidth.html
 // TODO: Action properties, ref: https://www.activestate.com/resources/quick-reads/how-to-display-data-in-a-table-using-tkinter/
 pub struct Table {
   columns: Option<Box<dyn TableColumns>>,
@@ -717,7 +775,7 @@ impl Table {
     // Compute fixed columns total width and accumulate the weight for flexible columns for later distribution
     for (col, column) in input_layout.iter().enumerate() {
       // Prepend the column separator, starting from the second column on
-      let separator_len = if col > 0 { 1 } else { 0 };
+      let separator_len = if col > 0 { self.layout.column_separator_width } else { 0 };
       fixed_width += column.min + separator_len;
       final_widths.push(column.min);
       flex_total_weight += column.weight;
@@ -859,7 +917,7 @@ impl Table {
       let column = columns.column(col).unwrap();
 
       // Prepend the column separator, starting from the second column on
-      let separator_len = if col > 0 { 1 } else { 0 };
+      let separator_len = if col > 0 { self.layout.column_separator_width } else { 0 };
 
       // Check if we still have space for minimum column separator
       if !avail_table_size.contains(Size2D::new(separator_len, 1)) {
@@ -1017,6 +1075,18 @@ impl Table {
       Err(LayoutError::InsufficientSpace)
     }
   }
+
+  /// Row and column of the cells under a column, in the order they are visited after the columns.
+  fn cell_positions(&self) -> Vec<(usize, usize)> {
+    let columns = self.columns.as_ref().map_or(0, |columns| columns.len());
+    match self.data.as_ref() {
+      Some(data) => (0..data.rows_len())
+        .flat_map(|row| (0..columns).map(move |col| (row, col)))
+        .filter(|(row, col)| data.cell(*row, *col).is_some())
+        .collect(),
+      None => Vec::new(),
+    }
+  }
 }
 
 #[derive(Debug)]
@@ -1028,54 +1098,10 @@ struct ColumnLayoutFlexInput {
 
 impl Widget for Table {
   fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
-    // NOTE: pass down to the column, make it possible for the column to spawn a Popup Menu with filled options,
-    // as we go back up the hierarchy the Popup can be filled up.
-
-    let (_, layout) = self.layout_table(size).unwrap();
-    let flexed_widths = self.layout_flex(size, layout).unwrap();
-    let mut column_starts = Vec::new();
-    for (idx, width) in flexed_widths.iter().enumerate() {
-      let prev = *column_starts.get(idx.checked_sub(1).unwrap_or(0)).unwrap_or(&0);
-      let new = prev + 1 + *width;
-      column_starts.push(new);
-    }
-
-    match event {
-      AnyEvent::Input(input) => match input {
-        Event::Key(_) => EventResult::Unhandled,
-        Event::Mouse(mouse) => {
-          let mut col = None;
-          for idx in 0..column_starts.len() {
-            if mouse.column >= *column_starts.get(idx.checked_sub(1).unwrap_or(0)).unwrap_or(&0) as u16
-              && mouse.column < *column_starts.get(idx).unwrap_or(&std::usize::MAX) as u16
-            {
-              col = Some(idx);
-              break;
-            }
-          }
-
-          if mouse.modifiers.is_empty() {
-            match mouse.kind {
-              MouseEventKind::Down(button) => match button {
-                MouseButton::Left => EventResult::Unhandled,
-                MouseButton::Right => EventResult::Unhandled,
-                MouseButton::Middle => {
-                  if let Some(col) = col {
-                    let mut column = self.columns.as_mut().unwrap().column_mut(col).unwrap();
-                    column.as_mut_widget().event(event, size);
-                  }
-                  EventResult::Done
-                }
-              },
-              _ => EventResult::Unhandled,
-            }
-          } else {
-            EventResult::Unhandled
-          }
-        }
-        Event::Resize(_, _) => EventResult::Unhandled,
-      },
-    }
+    // NOTE: a click on a heading is routed to its column, being recorded where it was rendered,
+    // and what the column leaves unhandled bubbles up to here. The column may spawn a Popup Menu
+    // with filled options, as we go back up the hierarchy the Popup can be filled up.
+    EventResult::Unhandled
   }
 
   fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
@@ -1102,10 +1128,10 @@ impl Widget for Table {
         the_x += if col > 0 {
           let child_frame = Rect::new(
             Point2D::new(ctx.get_frame().min_x() + the_x, ctx.get_frame().min_y()),
-            Size2D::new(1, 1 /* TODO: height */),
+            Size2D::new(self.layout.column_separator_width, 1 /* TODO: height */),
           );
-          ctx.render_child_widget(child_frame, &self.layout.column_separator);
-          1
+          ctx.render_child_widget(child_frame, &FillChar::new(self.layout.column_separator));
+          self.layout.column_separator_width
         } else {
           0
         };
@@ -1138,10 +1164,10 @@ impl Widget for Table {
               ctx.get_frame().min_x() + the_x,
               ctx.get_frame().min_y() + row + column_heading_height,
             ),
-            Size2D::new(flexed_widths[col], 1 /* TODO: height */),
+            Size2D::new(self.layout.column_separator_width, 1 /* TODO: height */),
           );
-          ctx.render_child_widget(child_frame, &self.layout.column_separator);
-          1
+          ctx.render_child_widget(child_frame, &FillChar::new(self.layout.column_separator));
+          self.layout.column_separator_width
         } else {
           0
         };
@@ -1165,7 +1191,70 @@ impl Widget for Table {
     Ok(())
   }
 
-  fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+  /// Visits the columns, their headings are rendered as the column widgets, then the cells row by row.
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    if let Some(columns) = self.columns.as_ref() {
+      for idx in 0..columns.len() {
+        if let Some(column) = columns.column(idx) {
+          visitor(column.as_widget());
+        }
+      }
+    }
+    if let Some(data) = self.data.as_ref() {
+      for (row, col) in self.cell_positions() {
+        if let Some(cell) = data.cell(row, col) {
+          visitor(cell.deref());
+        }
+      }
+    }
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    if let Some(columns) = self.columns.as_mut() {
+      for idx in 0..columns.len() {
+        if let Some(mut column) = columns.column_mut(idx) {
+          visitor(column.as_mut_widget());
+        }
+      }
+    }
+    let positions = self.cell_positions();
+    if let Some(data) = self.data.as_mut() {
+      for (row, col) in positions {
+        if let Some(mut cell) = data.cell_mut(row, col) {
+          visitor(cell.deref_mut());
+        }
+      }
+    }
+  }
+
+  /// Generated columns and cells are not kept, so they are not lent.
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    let columns = self.columns.as_ref().map_or(0, |columns| columns.len());
+    if idx < columns {
+      return match self.columns.as_ref()?.column(idx)? {
+        Scoped::Ref(column) => Some(column.as_widget()),
+        Scoped::Box(_) => None,
+      };
+    }
+    let (row, col) = *self.cell_positions().get(idx - columns)?;
+    match self.data.as_ref()?.cell(row, col)? {
+      Scoped::Ref(cell) => Some(cell),
+      Scoped::Box(_) => None,
+    }
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    let columns = self.columns.as_ref().map_or(0, |columns| columns.len());
+    if idx < columns {
+      return match self.columns.as_mut()?.column_mut(idx)? {
+        ScopedMut::Ref(column) => Some(column.as_mut_widget()),
+        ScopedMut::Box(_) => None,
+      };
+    }
+    let (row, col) = *self.cell_positions().get(idx - columns)?;
+    match self.data.as_mut()?.cell_mut(row, col)? {
+      ScopedMut::Ref(cell) => Some(cell),
+      ScopedMut::Box(_) => None,
+    }
   }
 }
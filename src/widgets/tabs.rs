@@ -1 +1,395 @@
+use crossterm::style::{Attribute, ContentStyle};
+use euclid::default::{Point2D, Rect, Size2D};
 
+use crate::render::RenderCtx;
+use crate::tree;
+use crate::widgets::{
+  AnyEvent, Capability, EventResult, FillChar, LayoutError, LayoutResult, LayoutSize, MouseGesture, RenderResult,
+  Widget,
+};
+use crate::{Color, Event, KeyCode, MouseEventKind, Style};
+
+/// Pages switched from a bar of titles, only the active page is laid out and rendered.
+/// The titles are drawn as `┤title├` over a line, the way the `windows` example draws its title bar.
+/// When they don't all fit, the bar scrolls to the active title and shows `◀`/`▶` at the ends where titles
+/// are hidden, a title too long for the bar on its own is truncated with `…`.
+/// The bar takes the focus, Left/Right, Home/End and the mouse wheel switch pages, as does a click on a title
+/// or on an arrow. The widgets of the page left lose the focus, which goes back to the first focusable widget.
+pub struct Tabs {
+  bar: TabBar,
+  pages: Vec<Box<dyn Widget>>,
+}
+
+impl Tabs {
+  pub fn new() -> Self {
+    Self {
+      bar: TabBar {
+        titles: Vec::new(),
+        active: 0,
+        focused: false,
+        line_style: Style::new().fg(Color::DarkGreen),
+        active_style: Style::new().fg(Color::White).bg(Color::DarkGreen).bold(),
+        inactive_style: Style::new(),
+      },
+      pages: Vec::new(),
+    }
+  }
+
+  pub fn tab<Title: Widget + 'static, Page: Widget + 'static>(mut self, title: Title, page: Page) -> Self {
+    self.bar.titles.push(Box::new(title));
+    self.pages.push(Box::new(page));
+    self
+  }
+
+  pub fn active(mut self, idx: usize) -> Self {
+    self.set_active(idx);
+    self
+  }
+
+  /// Style of the line and of the `┤├` around the titles.
+  pub fn line_style(mut self, style: Style) -> Self {
+    self.bar.line_style = style;
+    self
+  }
+
+  pub fn active_style(mut self, style: Style) -> Self {
+    self.bar.active_style = style;
+    self
+  }
+
+  pub fn inactive_style(mut self, style: Style) -> Self {
+    self.bar.inactive_style = style;
+    self
+  }
+
+  /// Index of the active tab.
+  pub fn get_active(&self) -> usize {
+    self.bar.active
+  }
+
+  /// Switch to the tab at the index, the last one if past the end.
+  pub fn set_active(&mut self, idx: usize) {
+    // out of the event, the frame of the page is not known
+    self.switch(idx, &Size2D::zero());
+  }
+
+  /// Switch tabs, telling the widgets of the page left that they lost the focus, if one of them had it.
+  fn switch(&mut self, idx: usize, page_size: &Size2D<usize>) {
+    let left = self.bar.active;
+    self.bar.select(idx);
+    if self.bar.active == left {
+      return;
+    }
+    if let Some(page) = self.pages.get_mut(left) {
+      tree::walk_mut(page.as_mut(), &mut |widget, _| {
+        widget.event(&AnyEvent::FocusOut, page_size);
+      });
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.pages.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.pages.is_empty()
+  }
+}
+
+impl Default for Tabs {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Widget for Tabs {
+  /// Handles the input the bar leaves unhandled, for the switch to reach the pages.
+  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
+    let idx = match event {
+      AnyEvent::Input(Event::Key(key)) if self.bar.focused => match key.code {
+        KeyCode::Left => self.bar.step(-1),
+        KeyCode::Right => self.bar.step(1),
+        KeyCode::Home => 0,
+        KeyCode::End => usize::MAX,
+        _ => return EventResult::Unhandled,
+      },
+      AnyEvent::Input(Event::Mouse(mouse)) if mouse.row == 0 => match mouse.kind {
+        MouseEventKind::Down(_) => {
+          let placement = self.bar.placement(size.width);
+          match placement.tab_at(mouse.column as usize, size.width, self.bar.active) {
+            Some(idx) => idx,
+            None => return EventResult::Unhandled,
+          }
+        }
+        _ => return EventResult::Unhandled,
+      },
+      AnyEvent::Mouse(MouseGesture::Wheel { delta, position }) if position.y == 0 => self.bar.step(delta.signum()),
+      _ => return EventResult::Unhandled,
+    };
+    self.switch(idx, &Size2D::new(size.width, size.height.saturating_sub(1)));
+    EventResult::Done
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    if avail_size.width == 0 || avail_size.height == 0 {
+      return Err(LayoutError::InsufficientSpace);
+    }
+    let page_size = Size2D::new(avail_size.width, avail_size.height - 1);
+    let mut layout = match self.pages.get(self.bar.active) {
+      Some(page) => page.layout(&page_size)?,
+      None => Default::default(),
+    };
+    // the bar spans the whole width
+    layout.min.width = layout.min.width.max(1);
+    layout.max.width = avail_size.width;
+    layout.min.height += 1;
+    layout.max.height += 1;
+    Ok(layout)
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = *ctx.get_frame();
+    let bar = Rect::new(frame.origin, Size2D::new(frame.width(), 1));
+    ctx.render_child_widget(bar, &self.bar)?;
+    match self.pages.get(self.bar.active) {
+      Some(page) if frame.height() > 1 => {
+        let page_frame = Rect::new(
+          Point2D::new(frame.min_x(), frame.min_y() + 1),
+          Size2D::new(frame.width(), frame.height() - 1),
+        );
+        ctx.render_child_dyn_widget(page_frame, page.as_ref())
+      }
+      _ => Ok(()),
+    }
+  }
+
+  /// The bar, then each page, an empty widget standing in for the inactive ones.
+  /// Each page having an index of its own, the focus on a page left is not taken for a widget of the new page.
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    visitor(&self.bar);
+    for (idx, page) in self.pages.iter().enumerate() {
+      if idx == self.bar.active {
+        visitor(page.as_ref());
+      } else {
+        visitor(&());
+      }
+    }
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    visitor(&mut self.bar);
+    for (idx, page) in self.pages.iter_mut().enumerate() {
+      if idx == self.bar.active {
+        visitor(page.as_mut());
+      } else {
+        visitor(&mut ());
+      }
+    }
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    match idx {
+      0 => Some(&self.bar),
+      idx if idx == self.bar.active + 1 => Some(self.pages.get(self.bar.active)?.as_ref()),
+      _ => None,
+    }
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    match idx {
+      0 => Some(&mut self.bar),
+      idx if idx == self.bar.active + 1 => Some(self.pages.get_mut(self.bar.active)?.as_mut()),
+      _ => None,
+    }
+  }
+}
+
+/// Title of a tab placed on the bar.
+struct Placed {
+  idx: usize,
+  /// First column of the `┤title├`.
+  x: usize,
+  /// Width of the title, without the `┤├`.
+  width: usize,
+  truncated: bool,
+}
+
+/// Titles placed on a bar of some width, with the arrows showing where titles are hidden.
+struct Placement {
+  titles: Vec<Placed>,
+  left_arrow: bool,
+  right_arrow: bool,
+}
+
+impl Placement {
+  /// Tab under the column, arrows select the tab next to the active one.
+  fn tab_at(&self, x: usize, width: usize, active: usize) -> Option<usize> {
+    if self.left_arrow && x == 0 {
+      return Some(active.saturating_sub(1));
+    }
+    if self.right_arrow && x + 1 == width {
+      return Some(active + 1);
+    }
+    let placed = self
+      .titles
+      .iter()
+      .find(|placed| x >= placed.x && x < placed.x + placed.width + 2)?;
+    Some(placed.idx)
+  }
+}
+
+/// Row of titles on top of the pages, focusable apart from the pages.
+/// The input switching pages is left to the tabs, which switch the pages along.
+struct TabBar {
+  titles: Vec<Box<dyn Widget>>,
+  active: usize,
+  focused: bool,
+  line_style: Style,
+  active_style: Style,
+  inactive_style: Style,
+}
+
+impl TabBar {
+  fn select(&mut self, idx: usize) {
+    self.active = idx.min(self.titles.len().saturating_sub(1));
+  }
+
+  /// Index of the tab some steps away from the active one, not before the first.
+  fn step(&self, by: isize) -> usize {
+    (self.active as isize).saturating_add(by).max(0) as usize
+  }
+
+  /// Widths the titles take when the bar is as wide as given.
+  fn title_widths(&self, width: usize) -> Vec<usize> {
+    let avail = Size2D::new(width, 1);
+    let title_width = |title: &dyn Widget| title.layout(&avail).map_or(0, |layout| layout.max.width.min(width));
+    self.titles.iter().map(|title| title_width(title.as_ref())).collect()
+  }
+
+  /// Place the titles from the first one that still leaves room for the active title.
+  /// The columns at both ends are kept for the arrows.
+  fn placement(&self, width: usize) -> Placement {
+    let widths = self.title_widths(width);
+    let room = width.saturating_sub(2);
+    let mut first = self.active.min(widths.len());
+    let mut used = widths.get(first).map_or(0, |width| width + 2);
+    while first > 0 && used + widths[first - 1] + 2 <= room {
+      first -= 1;
+      used += widths[first] + 2;
+    }
+
+    let mut titles = Vec::new();
+    let mut x = 1;
+    for (idx, &title_width) in widths.iter().enumerate().skip(first) {
+      if x + title_width + 2 <= room + 1 {
+        titles.push(Placed {
+          idx,
+          x,
+          width: title_width,
+          truncated: false,
+        });
+        x += title_width + 2;
+      } else {
+        // the active title alone is too long for the bar
+        if titles.is_empty() && room > 2 {
+          titles.push(Placed {
+            idx,
+            x,
+            width: room - 2,
+            truncated: true,
+          });
+        }
+        break;
+      }
+    }
+    let shown = titles.last().map_or(first, |placed| placed.idx + 1);
+    Placement {
+      titles,
+      left_arrow: first > 0,
+      right_arrow: shown < widths.len(),
+    }
+  }
+}
+
+impl Widget for TabBar {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::FocusIn => {
+        self.focused = true;
+        EventResult::Done
+      }
+      AnyEvent::FocusOut => {
+        self.focused = false;
+        EventResult::Done
+      }
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let size = Size2D::new(avail_size.width, 1);
+    if !avail_size.contains(Size2D::new(1, 1)) {
+      return Err(LayoutError::InsufficientSpace);
+    }
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), size))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = *ctx.get_frame();
+    let line = ContentStyle::from(&self.line_style);
+    let cell =
+      |x: usize, width: usize| Rect::new(Point2D::new(frame.min_x() + x, frame.min_y()), Size2D::new(width, 1));
+    ctx.render_child_widget_styled(frame, &line, &FillChar::new('─'))?;
+
+    let placement = self.placement(frame.width());
+    for placed in &placement.titles {
+      let mut style = if placed.idx == self.active {
+        ContentStyle::from(&self.active_style)
+      } else {
+        ContentStyle::from(&self.inactive_style)
+      };
+      if self.focused && placed.idx == self.active {
+        style.attributes = style.attributes | Attribute::Underlined;
+      }
+      ctx.render_child_widget_styled(cell(placed.x, 1), &line, &'┤')?;
+      let title = cell(placed.x + 1, placed.width);
+      ctx.render_child_dyn_widget_styled(title, &style, self.titles[placed.idx].as_ref())?;
+      // over whatever the title shows in its last cell when cut
+      if placed.truncated {
+        ctx.render_child_widget_styled(cell(placed.x + placed.width, 1), &style, &'…')?;
+      }
+      ctx.render_child_widget_styled(cell(placed.x + placed.width + 1, 1), &line, &'├')?;
+    }
+    if placement.left_arrow {
+      ctx.render_child_widget_styled(cell(0, 1), &line, &'◀')?;
+    }
+    if placement.right_arrow {
+      ctx.render_child_widget_styled(cell(frame.width() - 1, 1), &line, &'▶')?;
+    }
+    Ok(())
+  }
+
+  fn has_capability(&self, capability: &Capability) -> bool {
+    matches!(capability, Capability::Selectable)
+  }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    for title in &self.titles {
+      visitor(title.as_ref());
+    }
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    for title in &mut self.titles {
+      visitor(title.as_mut());
+    }
+  }
+
+  fn get_child(&self, idx: usize) -> Option<&dyn Widget> {
+    Some(self.titles.get(idx)?.as_ref())
+  }
+
+  fn get_child_mut(&mut self, idx: usize) -> Option<&mut dyn Widget> {
+    Some(self.titles.get_mut(idx)?.as_mut())
+  }
+}
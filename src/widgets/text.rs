@@ -1,4 +1,5 @@
 use crate::render::RenderCtx;
+use crate::util::display_width;
 use crate::widgets::{AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderResult, Widget};
 use euclid::default::Size2D;
 use std::borrow::Cow;
@@ -58,7 +59,7 @@ impl Widget for Text {
     let wrapped_text = textwrap::wrap(self.data.as_str(), textwrap::Options::new(avail_size.width));
     let mut longest = 0;
     for line in wrapped_text.iter() {
-      let count = line.as_ref().chars().count();
+      let count = display_width(line.as_ref());
       if count > longest {
         longest = count;
       }
@@ -81,12 +82,12 @@ impl Widget for Text {
               ctx.renderer().write(line.as_ref());
             }
             TextAlign::Center => {
-              let padding = frame.width() - line.as_ref().chars().count();
+              let padding = frame.width() - display_width(line.as_ref());
               ctx.renderer().move_right(padding / 2);
               ctx.renderer().write(line.as_ref());
             }
             TextAlign::Right => {
-              let padding = frame.width() - line.as_ref().chars().count();
+              let padding = frame.width() - display_width(line.as_ref());
               ctx.renderer().move_right(padding);
               ctx.renderer().write(line.as_ref());
             }
@@ -1,19 +1,23 @@
 use crate::render::RenderCtx;
+use crate::util::display_width;
 use crate::widgets::{
   AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderResult, Text, Widget,
 };
 use crate::{Event, KeyCode, KeyModifiers};
 use crossterm::event::KeyEvent;
-use euclid::default::Size2D;
+use crossterm::style::{ContentStyle, Stylize};
+use euclid::default::{Point2D, Rect, Size2D};
 
 pub struct TextInput {
   pub text: Text,
+  focused: bool,
 }
 
 impl TextInput {
   pub fn new() -> Self {
     Self {
       text: Text::new(String::new()),
+      focused: false,
     }
   }
 
@@ -23,7 +27,23 @@ impl TextInput {
   }
 
   pub fn with_text(text: Text) -> Self {
-    Self { text }
+    Self { text, focused: false }
+  }
+
+  pub fn is_focused(&self) -> bool {
+    self.focused
+  }
+
+  /// Position of the cursor after the end of the wrapped text, relative to the frame.
+  fn cursor(&self, width: usize) -> Point2D<usize> {
+    let lines = textwrap::wrap(self.text.data.as_str(), textwrap::Options::new(width));
+    let last = lines.last().map(|line| display_width(line.as_ref())).unwrap_or(0);
+    let row = lines.len().saturating_sub(1);
+    if last >= width {
+      Point2D::new(0, row + 1)
+    } else {
+      Point2D::new(last, row)
+    }
   }
 }
 
@@ -67,6 +87,15 @@ impl Widget for TextInput {
         Event::Mouse(_) => EventResult::Unhandled,
         Event::Resize(_, _) => EventResult::Unhandled,
       },
+      AnyEvent::FocusIn => {
+        self.focused = true;
+        EventResult::Done
+      }
+      AnyEvent::FocusOut => {
+        self.focused = false;
+        EventResult::Done
+      }
+      AnyEvent::Mouse(_) | AnyEvent::FocusWithin(_) | AnyEvent::Tick | AnyEvent::Custom(_) => EventResult::Unhandled,
     }
   }
 
@@ -75,10 +104,19 @@ impl Widget for TextInput {
   }
 
   fn render(&self, ctx: &RenderCtx) -> RenderResult {
-    ctx.render_child_widget(ctx.get_frame().clone(), &self.text)
+    let frame = ctx.get_frame().clone();
+    ctx.render_child_widget(frame, &self.text)?;
+    if self.focused {
+      let cursor = self.cursor(frame.width());
+      if cursor.y < frame.height() {
+        let cursor_frame = Rect::new(frame.origin + cursor.to_vector(), Size2D::new(1, 1));
+        ctx.render_child_widget_styled(cursor_frame, &ContentStyle::new().reverse(), &' ')?;
+      }
+    }
+    Ok(())
   }
 
   fn has_capability(&self, capability: &Capability) -> bool {
-    todo!()
+    matches!(capability, Capability::Selectable)
   }
 }
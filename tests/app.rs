@@ -0,0 +1,77 @@
+use std::thread;
+
+use euclid::default::Size2D;
+
+use witui::app::App;
+use witui::testing::Driver;
+use witui::widgets::{AnyEvent, Capability, EventResult, Hook, MouseGesture, RowView, Widget};
+use witui::{Event, KeyCode, WiTui};
+
+struct Counter;
+
+enum Msg {
+  Add(i32),
+  Set(i32),
+}
+
+/// Label emitting the message when clicked, or when focused and '+' is pressed.
+fn button(label: &'static str, msg: fn() -> Msg) -> Hook<&'static str> {
+  Hook::child(label)
+    .on_event(move |_, event, _| match event {
+      AnyEvent::Mouse(MouseGesture::Click { .. }) => EventResult::message(msg()),
+      AnyEvent::Input(Event::Key(key)) if key.code == KeyCode::Char('+') => EventResult::message(msg()),
+      _ => EventResult::Unhandled,
+    })
+    .on_has_capability(|_, capability| matches!(capability, Capability::Selectable))
+}
+
+impl App for Counter {
+  type Model = i32;
+  type Msg = Msg;
+
+  fn update(model: &mut i32, msg: Msg) {
+    match msg {
+      Msg::Add(delta) => *model += delta,
+      Msg::Set(value) => *model = value,
+    }
+  }
+
+  fn view(model: &i32) -> impl Widget + 'static {
+    RowView::new()
+      .child(button("+", || Msg::Add(1)))
+      .child(button("-", || Msg::Add(-1)))
+      .child(format!("{:>3}", model))
+  }
+}
+
+#[test]
+fn clicks_update_the_model_and_rebuild_the_view() {
+  let mut driver = Driver::with_tui(WiTui::app::<Counter>(0), Size2D::new(5, 1));
+  assert_eq!(driver.lines(), vec!["+-  0"]);
+  driver.click(0, 0);
+  driver.click(0, 0);
+  assert_eq!(driver.lines(), vec!["+-  2"]);
+  driver.click(1, 0);
+  assert_eq!(driver.lines(), vec!["+-  1"]);
+}
+
+#[test]
+fn focus_survives_the_rebuild() {
+  let mut driver = Driver::with_tui(WiTui::app::<Counter>(0), Size2D::new(5, 1));
+  driver.key(KeyCode::Tab);
+  driver.key(KeyCode::Char('+'));
+  driver.key(KeyCode::Char('+'));
+  assert_eq!(driver.lines(), vec!["+- -2"]);
+}
+
+#[test]
+fn handle_messages_go_to_update() {
+  let tui = WiTui::app::<Counter>(0);
+  let handle = tui.handle();
+  let mut driver = Driver::with_tui(tui, Size2D::new(5, 1));
+  thread::spawn(move || handle.send(Msg::Set(42)).unwrap())
+    .join()
+    .unwrap();
+  assert!(driver.receive());
+  assert_eq!(driver.lines(), vec!["+- 42"]);
+}
@@ -0,0 +1,111 @@
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use euclid::default::{Point2D, Size2D};
+
+use witui::backend::{Backend, CrosstermBackend, HeadlessBackend};
+use witui::widgets::RenderError;
+use witui::WiTui;
+
+/// Writer whose output is kept after the backend owning it is dropped.
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Shared {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.borrow_mut().extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Backend without a terminal behind it, as when stdout is not a tty.
+struct NoTty {
+  raw_mode: Rc<RefCell<bool>>,
+}
+
+impl Backend for NoTty {
+  fn size(&self) -> std::io::Result<Size2D<usize>> {
+    Err(std::io::Error::other("not a tty"))
+  }
+
+  fn enable_raw_mode(&mut self) -> std::io::Result<()> {
+    *self.raw_mode.borrow_mut() = true;
+    Err(std::io::Error::other("not a tty"))
+  }
+
+  fn disable_raw_mode(&mut self) -> std::io::Result<()> {
+    Err(std::io::Error::other("not a tty"))
+  }
+
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>> {
+    Err(std::io::Error::other("not a tty"))
+  }
+
+  fn write(&mut self, _buf: &[u8]) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[test]
+fn sized_crossterm_backend_writes_without_a_terminal() {
+  let out = Shared::default();
+  let mut tui = WiTui::root_widget("hello").backend(CrosstermBackend::with_size(out.clone(), Size2D::new(10, 2)));
+  tui.print().unwrap();
+  drop(tui);
+  let output = String::from_utf8(out.0.borrow().clone()).unwrap();
+  assert!(output.contains("hello"), "{:?}", output);
+}
+
+#[test]
+fn sized_crossterm_backend_does_not_query_the_terminal() {
+  let mut backend = CrosstermBackend::with_size(Vec::new(), Size2D::new(10, 2));
+  assert_eq!(backend.size().unwrap(), Size2D::new(10, 2));
+  assert_eq!(backend.cursor_position().unwrap(), Point2D::zero());
+  backend.enable_raw_mode().unwrap();
+  backend.disable_raw_mode().unwrap();
+}
+
+#[test]
+fn terminal_failing_to_set_up_is_an_error() {
+  let raw_mode = Rc::new(RefCell::new(false));
+  let backend = NoTty {
+    raw_mode: raw_mode.clone(),
+  };
+  let mut tui = WiTui::root_widget("hello").backend(backend).alternate(true);
+  assert!(matches!(tui.print(), Err(RenderError::Io(_))));
+  assert!(!*raw_mode.borrow());
+  // not retried with the default backend
+  assert!(matches!(tui.run_loop(), Err(RenderError::Io(_))));
+}
+
+#[test]
+fn headless_loop_leaves_the_terminal_input_alone() {
+  let mut tui = WiTui::root_widget("wait")
+    .backend(HeadlessBackend::new(Size2D::new(4, 1)))
+    .alternate(true);
+  let handle = tui.handle();
+  thread::spawn(move || {
+    thread::sleep(Duration::from_millis(100));
+    handle.update(|tui: &mut WiTui| tui.quit()).unwrap();
+  });
+  // reading the terminal would fail without one, or take the input of the one running the tests
+  tui.run_loop().unwrap();
+}
+
+#[test]
+fn only_terminal_backends_read_its_input() {
+  assert!(CrosstermBackend::new(Vec::new()).reads_terminal_input());
+  assert!(!CrosstermBackend::with_size(Vec::new(), Size2D::new(10, 2)).reads_terminal_input());
+  assert!(!HeadlessBackend::new(Size2D::new(10, 2)).reads_terminal_input());
+}
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use euclid::default::Size2D;
+
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::widgets::{
+  Align, AnyEvent, Borders, Button, Capability, ColumnView, EventResult, Expanded, Flexible, Hook, Id, LayoutResult,
+  LayoutSize, Leak, Min, Padding, RenderResult, Repeat, RowView, ScrollView, Scrollbar, Stack, Tabs, Widget,
+};
+use witui::{Event, KeyCode, Style, WiTui};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Focusable widget logging the keys and messages it gets.
+struct Recorder(Log);
+
+impl Widget for Recorder {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    let entry = match event {
+      AnyEvent::Input(Event::Key(key)) => format!("key {:?}", key.code),
+      AnyEvent::Custom(message) => match message.downcast_ref::<&str>() {
+        Some(message) => format!("custom {}", message),
+        None => return EventResult::Unhandled,
+      },
+      _ => return EventResult::Unhandled,
+    };
+    self.0.borrow_mut().push(entry);
+    EventResult::Done
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), *avail_size))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write("r");
+    Ok(())
+  }
+
+  fn has_capability(&self, capability: &Capability) -> bool {
+    matches!(capability, Capability::Selectable)
+  }
+}
+
+fn containers(log: &Log) -> Vec<(&'static str, Box<dyn Widget>)> {
+  let child = || Recorder(log.clone());
+  vec![
+    ("Align", Box::new(Align::center(child()))),
+    ("Borders", Box::new(Borders::new().borders_line(Style::default()).child(child()))),
+    ("Button", Box::new(Button::child(child()))),
+    ("ColumnView", Box::new(ColumnView::new().child(child()))),
+    ("Expanded", Box::new(Expanded::child(child()))),
+    ("Flexible", Box::new(Flexible::tight(1).child(child()))),
+    ("Hook", Box::new(Hook::child(child()))),
+    ("Id", Box::new(Id::new("recorder").child(child()))),
+    ("Leak", Box::new(Leak::child(child()))),
+    ("Min", Box::new(Min::zero().child(child()))),
+    ("Padding", Box::new(Padding::all(1).child(child()))),
+    ("Repeat", Box::new(Repeat::child(child()))),
+    ("RowView", Box::new(RowView::new().child(child()))),
+    ("Scrolled", Box::new(Scrollbar::vertical().attach(child()))),
+    ("ScrollView", Box::new(ScrollView::new().child(child()))),
+    ("Stack", Box::new(Stack::new().child(child()))),
+    ("Styled", Box::new(Style::new().bold().child(child()))),
+    ("Tabs", Box::new(Tabs::new().tab("tab", child()))),
+  ]
+}
+
+#[test]
+fn keys_and_messages_reach_the_child_of_every_container() {
+  let log = Log::default();
+  for (name, container) in containers(&log) {
+    log.borrow_mut().clear();
+    let tui = WiTui::root_widget(container);
+    let handle = tui.handle();
+    let mut driver = Driver::with_tui(tui, Size2D::new(8, 6));
+    if name == "Tabs" {
+      // from the tab bar to the page
+      driver.key(KeyCode::Tab);
+    }
+
+    assert!(matches!(driver.key(KeyCode::Char('k')), EventResult::Done), "{}", name);
+    handle.send("ping").unwrap();
+    assert!(driver.receive(), "{}", name);
+    assert_eq!(*log.borrow(), vec!["key Char('k')", "custom ping"], "{}", name);
+  }
+}
@@ -0,0 +1,76 @@
+use euclid::default::Size2D;
+
+use witui::testing::Driver;
+use witui::tree::walk;
+use witui::widgets::{AnyEvent, Borders, CheckBox, EventResult, Id, MouseGesture, Widget};
+use witui::{Style, WiTui};
+
+#[derive(witui_derive::Widget)]
+struct Card<Child> {
+  #[allow(dead_code)]
+  title: String,
+  #[child]
+  content: Child,
+}
+
+#[derive(witui_derive::Widget)]
+struct Wrap(#[allow(dead_code)] u32, #[child] &'static str);
+
+#[derive(witui_derive::Widget)]
+struct Labeled {
+  #[child]
+  check: CheckBox,
+}
+
+#[test]
+fn forwards_layout_render_and_capabilities() {
+  let card = Card {
+    title: String::from("card"),
+    content: Borders::new().borders_line(Style::default()).child(Wrap(7, "hi")),
+  };
+  assert_eq!(card.layout(&Size2D::new(10, 10)).unwrap().min, Size2D::new(3, 3));
+  let mut driver = Driver::new(card, Size2D::new(4, 3));
+  assert_eq!(driver.lines(), vec!["┌──┐", "│hi│", "└──┘"]);
+
+  let card = Card {
+    title: String::new(),
+    content: CheckBox::new(),
+  };
+  assert!(card.has_capability(&witui::widgets::Capability::Selectable));
+}
+
+#[test]
+fn child_is_visited() {
+  let mut count = 0;
+  walk(
+    &Card {
+      title: String::new(),
+      content: Wrap(0, "x"),
+    },
+    &mut |_, _| count += 1,
+  );
+  assert_eq!(count, 3);
+}
+
+#[test]
+fn child_is_found() {
+  let card = Card {
+    title: String::new(),
+    content: Id::new("content").child(String::from("x")),
+  };
+  let tui = WiTui::root_widget(card);
+  assert_eq!(tui.find::<String>("content").map(String::as_str), Some("x"));
+}
+
+#[test]
+fn events_reach_the_child_first() {
+  let mut driver = Driver::new(Labeled { check: CheckBox::new() }, Size2D::new(1, 1));
+  driver.click(0, 0);
+  assert!(driver.widget().check.is_marked());
+  // the derived widget leaves the events unhandled, the default of the trait
+  let event = AnyEvent::Mouse(MouseGesture::HoverEnter);
+  assert!(matches!(
+    driver.widget_mut().event(&event, &Size2D::new(1, 1)),
+    EventResult::Unhandled
+  ));
+}
@@ -0,0 +1,129 @@
+use crossterm::event::MouseButton;
+use euclid::default::Size2D;
+
+use witui::testing::Driver;
+use witui::widgets::table::{Column, ColumnWidthValue, Table};
+use witui::widgets::{CheckBox, EventResult, TextInput};
+use witui::{KeyCode, KeyModifiers, WiTui};
+
+#[test]
+fn text_input_typing() {
+  let mut driver = Driver::new(TextInput::new(), Size2D::new(10, 2));
+  driver.type_str("Hey\nyo");
+  assert_eq!(driver.widget().text.data, "Hey\nyo");
+  assert_eq!(driver.lines(), vec!["Hey       ", "yo        "]);
+
+  assert!(matches!(driver.key(KeyCode::Backspace), EventResult::Done));
+  assert!(matches!(driver.key(KeyCode::Esc), EventResult::Unhandled));
+  assert!(matches!(
+    driver.key_with(KeyCode::Char('x'), KeyModifiers::CONTROL),
+    EventResult::Unhandled
+  ));
+  assert_eq!(driver.widget().text.data, "Hey\ny");
+  assert_eq!(driver.lines(), vec!["Hey       ", "y         "]);
+}
+
+#[test]
+fn checkbox_click_toggles() {
+  let mut driver = Driver::new(CheckBox::new(), Size2D::new(1, 1));
+  assert_eq!(driver.lines(), vec!["○"]);
+
+  assert!(matches!(driver.click(0, 0), EventResult::Done));
+  assert!(driver.widget().is_marked());
+  assert_eq!(driver.lines(), vec!["◉"]);
+
+  driver.click_with(MouseButton::Right, 0, 0);
+  assert!(!driver.widget().is_marked());
+  assert_eq!(driver.lines(), vec!["○"]);
+}
+
+#[test]
+fn column_middle_click_toggles_width() {
+  let mut driver = Driver::new(Column::new("NAME"), Size2D::new(4, 1));
+  assert!(matches!(driver.widget().width.max, ColumnWidthValue::Auto));
+
+  assert!(matches!(driver.click(0, 0), EventResult::Unhandled));
+  assert!(matches!(driver.widget().width.max, ColumnWidthValue::Auto));
+
+  assert!(matches!(
+    driver.click_with(MouseButton::Middle, 0, 0),
+    EventResult::Done
+  ));
+  assert!(matches!(driver.widget().width.max, ColumnWidthValue::Fixed(1)));
+
+  driver.click_with(MouseButton::Middle, 0, 0);
+  assert!(matches!(driver.widget().width.max, ColumnWidthValue::Auto));
+}
+
+#[test]
+fn table_middle_click_collapses_column() {
+  let table = Table::new()
+    .columns(vec![Column::new("DEVICE"), Column::new("TYPE"), Column::new("STATE")])
+    .data(vec![
+      vec!["wlp8s0", "wifi", "connected"],
+      vec!["enp7s0", "ethernet", "unavailable"],
+    ]);
+  let mut driver = Driver::new(table, Size2D::new(30, 3));
+  assert_eq!(
+    driver.lines(),
+    vec![
+      "DEVICE TYPE     STATE         ",
+      "wlp8s0 wifi     connected     ",
+      "enp7s0 ethernet unavailable   "
+    ]
+  );
+
+  driver.click_with(MouseButton::Middle, 1, 0);
+  assert_eq!(
+    driver.lines(),
+    vec![
+      "… TYPE     STATE              ",
+      "… wifi     connected          ",
+      "… ethernet unavailable        "
+    ]
+  );
+
+  // the separator after the collapsed column is not part of it
+  assert!(matches!(
+    driver.click_with(MouseButton::Middle, 1, 0),
+    EventResult::Unhandled
+  ));
+  driver.click_with(MouseButton::Middle, 0, 0);
+  assert_eq!(
+    driver.lines(),
+    vec![
+      "DEVICE TYPE     STATE         ",
+      "wlp8s0 wifi     connected     ",
+      "enp7s0 ethernet unavailable   "
+    ]
+  );
+}
+
+#[test]
+fn resize_rerenders() {
+  let mut driver = Driver::new(TextInput::new(), Size2D::new(4, 1));
+  driver.type_str("abcdef");
+  assert_eq!(driver.lines(), vec!["abcd"]);
+
+  driver.resize(6, 2);
+  assert_eq!(driver.lines(), vec!["abcdef", "      "]);
+}
+
+#[test]
+fn tui_dispatch() {
+  let tui = WiTui::root_widget(CheckBox::new());
+  let mut driver = Driver::with_tui(tui, Size2D::new(2, 1));
+  assert_eq!(driver.lines(), vec!["○ "]);
+
+  driver.click(0, 0);
+  assert_eq!(driver.lines(), vec!["◉ "]);
+
+  // ctrl-c is taken by the event loop to quit
+  assert!(matches!(
+    driver.key_with(KeyCode::Char('c'), KeyModifiers::CONTROL),
+    EventResult::Unhandled
+  ));
+
+  driver.resize(3, 2);
+  assert_eq!(driver.lines(), vec!["◉  ", "   "]);
+}
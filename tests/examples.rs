@@ -0,0 +1,41 @@
+//! Regression tests rendering the examples against their snapshots in `tests/snapshots`.
+
+use euclid::default::Size2D;
+
+use witui::testing::assert_snapshot;
+
+#[allow(dead_code)]
+#[path = "../examples/nmcli.rs"]
+mod nmcli;
+
+#[allow(dead_code)]
+#[path = "../examples/paragraph.rs"]
+mod paragraph;
+
+#[allow(dead_code)]
+#[path = "../examples/spreadsheet.rs"]
+mod spreadsheet;
+
+#[allow(dead_code)]
+#[path = "../examples/windows.rs"]
+mod windows;
+
+#[test]
+fn nmcli() {
+  assert_snapshot("nmcli", &nmcli::root(), Size2D::new(80, 5));
+}
+
+#[test]
+fn paragraph() {
+  assert_snapshot("paragraph", &paragraph::root(), Size2D::new(80, 16));
+}
+
+#[test]
+fn spreadsheet() {
+  assert_snapshot("spreadsheet", &spreadsheet::root(), Size2D::new(155, 11));
+}
+
+#[test]
+fn windows() {
+  assert_snapshot("windows", &windows::root(), Size2D::new(32, 8));
+}
@@ -0,0 +1,83 @@
+use witui::widgets::table::{Column, Table};
+use witui::widgets::{Borders, ColumnView, FillChar, RowView, Widget};
+use witui::{render_to_ansi_string, render_to_html, render_to_string, render_to_svg, Color, Style};
+
+fn table() -> impl Widget {
+  Table::new()
+    .columns(vec![Column::new("DEVICE"), Column::new("STATE")])
+    .data(vec![vec!["wlp8s0", "connected"], vec!["lo", "unmanaged"]])
+}
+
+#[test]
+fn plain_text_takes_the_height_of_the_layout() {
+  assert_eq!(
+    render_to_string(&table(), 40).unwrap(),
+    "DEVICE STATE\nwlp8s0 connected\nlo     unmanaged\n"
+  );
+  // widgets filling any height get a single row
+  assert_eq!(render_to_string(&FillChar::new('#'), 3).unwrap(), "###\n");
+}
+
+#[test]
+fn plain_text_is_trimmed() {
+  let column = ColumnView::new().child("日本語").child("").child("abc");
+  assert_eq!(render_to_string(&column, 10).unwrap(), "日本語\n\nabc\n");
+}
+
+#[test]
+fn ansi_text_keeps_the_styles() {
+  let borders = Borders::new().borders_line(Style::new().green().bold()).child("Hi");
+  let text = render_to_ansi_string(&borders, 4).unwrap();
+  let lines: Vec<&str> = text.lines().collect();
+  assert_eq!(lines.len(), 3);
+  assert_eq!(
+    lines[1],
+    "\x1b[0m\x1b[1m\x1b[38;5;10m│\x1b[0mHi\x1b[0m\x1b[1m\x1b[38;5;10m│\x1b[0m"
+  );
+  // without the escape codes, the same as the plain text
+  let stripped: String = text
+    .split('\x1b')
+    .map(|part| part.split_once('m').map_or(part, |(_, rest)| rest))
+    .collect();
+  assert_eq!(stripped, render_to_string(&borders, 4).unwrap());
+}
+
+#[test]
+fn html_keeps_the_styles_in_spans() {
+  let row = RowView::new()
+    .child(Style::new().fg(Color::Rgb { r: 255, g: 136, b: 0 }).bold().child("<a>"))
+    .child(" & ")
+    .child(Style::new().bg(Color::AnsiValue(21)).italic().child("b"));
+  assert_eq!(
+    render_to_html(&row, 7).unwrap(),
+    "<pre style=\"font-family:monospace;color:#e5e5e5;background-color:#000000\">\
+     <span style=\"color:#ff8800;font-weight:bold\">&lt;a&gt;</span> &amp; \
+     <span style=\"background-color:#0000ff;font-style:italic\">b</span></pre>\n"
+  );
+}
+
+#[test]
+fn svg_draws_backgrounds_under_texts() {
+  let row = RowView::new()
+    .child(Style::new().reverse().child("ab"))
+    .child(Style::new().bg(Color::DarkBlue).child("  "))
+    .child(Style::new().fg(Color::Red).underlined().child("c"));
+  let svg = render_to_svg(&row, 6).unwrap();
+  let lines: Vec<&str> = svg.lines().collect();
+  assert_eq!(
+    lines,
+    vec![
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"54\" height=\"18\" viewBox=\"0 0 54 18\">",
+      "<rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>",
+      // reversed, the default foreground becomes the background
+      "<rect x=\"0\" y=\"0\" width=\"18\" height=\"18\" fill=\"#e5e5e5\"/>",
+      "<rect x=\"18\" y=\"0\" width=\"18\" height=\"18\" fill=\"#0000ee\"/>",
+      "<g font-family=\"monospace\" font-size=\"15px\" fill=\"#e5e5e5\" xml:space=\"preserve\">",
+      "<text x=\"0\" y=\"14\" textLength=\"18\" lengthAdjust=\"spacingAndGlyphs\" fill=\"#000000\">ab</text>",
+      "<text x=\"36\" y=\"14\" textLength=\"9\" lengthAdjust=\"spacingAndGlyphs\" fill=\"#ff0000\" \
+       text-decoration=\"underline\">c</text>",
+      "</g>",
+      "</svg>",
+    ]
+  );
+}
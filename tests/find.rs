@@ -0,0 +1,53 @@
+use euclid::default::Size2D;
+
+use witui::testing::Driver;
+use witui::widgets::{Borders, CheckBox, ColumnView, Id, Padding, RowView, Tabs, Widget};
+use witui::{KeyCode, Style, WiTui};
+
+fn tree() -> WiTui {
+  let rows: Vec<Box<dyn Widget>> = (0..3)
+    .map(|idx| Box::new(Id::new(idx).child(format!("row {}", idx))) as Box<dyn Widget>)
+    .collect();
+  let root = ColumnView::new()
+    .child(Id::new("title").child(String::from("Title")))
+    .child(RowView::new().child(Padding::default().child(Id::new("box").child(CheckBox::new()))))
+    .child(ColumnView::new().children(rows));
+  WiTui::root_widget(root)
+}
+
+#[test]
+fn find_by_name_and_number() {
+  let mut driver = Driver::with_tui(tree(), Size2D::new(6, 5));
+  assert_eq!(driver.lines(), vec!["Title ", "○     ", "row 0 ", "row 1 ", "row 2 "]);
+
+  let tui = driver.tui_mut();
+  tui.find_mut::<String>("title").unwrap().push('!');
+  *tui.find_mut::<String>(1).unwrap() = String::from("one");
+  driver.render();
+  assert_eq!(driver.lines(), vec!["Title!", "○     ", "row 0 ", "one   ", "row 2 "]);
+}
+
+#[test]
+fn found_widget_is_the_live_one() {
+  let mut driver = Driver::with_tui(tree(), Size2D::new(6, 5));
+  assert!(driver.tui().find::<CheckBox>("box").unwrap().is_focused());
+  driver.key(KeyCode::Char(' '));
+  assert!(driver.tui().find::<CheckBox>("box").unwrap().is_marked());
+}
+
+#[test]
+fn missing_id_or_other_type_is_none() {
+  let mut tui = tree();
+  assert!(tui.find_mut::<String>("nothing").is_none());
+  assert!(tui.find_mut::<String>("box").is_none());
+  assert!(tui.find::<String>(7).is_none());
+}
+
+#[test]
+fn find_reaches_through_decorators_and_pages() {
+  let page = Borders::new()
+    .borders_line(Style::default())
+    .child(Id::new("page").child(String::from("page")));
+  let tui = WiTui::root_widget(Tabs::new().tab("tab", page));
+  assert_eq!(tui.find::<String>("page").map(String::as_str), Some("page"));
+}
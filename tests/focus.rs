@@ -0,0 +1,126 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use euclid::default::Size2D;
+
+use witui::testing::Driver;
+use witui::widgets::table::Column;
+use witui::widgets::{AnyEvent, Borders, CheckBox, ColumnView, EventResult, Hook, RowView, TextInput, Widget};
+use witui::{Attribute, Event, KeyCode};
+
+fn reversed(driver: &mut Driver<impl witui::testing::DriverTarget>, x: usize, y: usize) -> bool {
+  driver.canvas().cell(x, y).unwrap().attributes().has(Attribute::Reverse)
+}
+
+fn two_inputs() -> impl Widget {
+  ColumnView::new().child(TextInput::new()).child(TextInput::new())
+}
+
+#[test]
+fn first_focusable_is_focused() {
+  let mut driver = Driver::new(two_inputs(), Size2D::new(6, 2));
+  assert!(reversed(&mut driver, 0, 0));
+  assert!(!reversed(&mut driver, 0, 1));
+}
+
+#[test]
+fn keys_go_to_focused_widget() {
+  let mut driver = Driver::new(two_inputs(), Size2D::new(6, 2));
+  driver.type_str("ab");
+  assert_eq!(driver.lines(), vec!["ab    ", "      "]);
+
+  assert!(matches!(driver.key(KeyCode::Tab), EventResult::Done));
+  driver.type_str("cd");
+  assert_eq!(driver.lines(), vec!["ab    ", "cd    "]);
+  assert!(!reversed(&mut driver, 2, 0));
+  assert!(reversed(&mut driver, 2, 1));
+}
+
+#[test]
+fn tab_wraps_around() {
+  let mut driver = Driver::new(two_inputs(), Size2D::new(6, 2));
+  driver.key(KeyCode::Tab);
+  driver.key(KeyCode::Tab);
+  driver.type_str("x");
+  assert_eq!(driver.lines(), vec!["x     ", "      "]);
+
+  driver.key(KeyCode::BackTab);
+  driver.type_str("y");
+  assert_eq!(driver.lines(), vec!["x     ", "y     "]);
+
+  driver.key(KeyCode::BackTab);
+  driver.type_str("z");
+  assert_eq!(driver.lines(), vec!["xz    ", "y     "]);
+}
+
+#[test]
+fn checkbox_toggles_with_space_when_focused() {
+  let root = RowView::new().child(CheckBox::new()).child(CheckBox::new());
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  assert_eq!(driver.lines(), vec!["○○"]);
+  assert!(reversed(&mut driver, 0, 0));
+
+  driver.key(KeyCode::Tab);
+  assert!(!reversed(&mut driver, 0, 0));
+  assert!(reversed(&mut driver, 1, 0));
+
+  assert!(matches!(driver.key(KeyCode::Char(' ')), EventResult::Done));
+  assert_eq!(driver.lines(), vec!["○◉"]);
+  driver.key(KeyCode::BackTab);
+  driver.key(KeyCode::Enter);
+  assert_eq!(driver.lines(), vec!["◉◉"]);
+}
+
+#[test]
+fn focus_events_reach_the_widget() {
+  let mut driver = Driver::new(CheckBox::new(), Size2D::new(1, 1));
+  assert!(driver.widget().is_focused());
+  assert!(matches!(driver.key(KeyCode::Tab), EventResult::Done));
+  assert!(driver.widget().is_focused());
+}
+
+#[test]
+fn keys_go_to_root_without_focusables() {
+  let mut driver = Driver::new(Column::new("NAME"), Size2D::new(4, 1));
+  assert!(matches!(driver.key(KeyCode::Tab), EventResult::Unhandled));
+}
+
+#[test]
+fn keys_reach_nested_widgets_without_focusables() {
+  let quit = Rc::new(Cell::new(false));
+  let seen = quit.clone();
+  let hook = Hook::child("x").on_event(move |_, event, _| match event {
+    AnyEvent::Input(Event::Key(key)) if key.code == KeyCode::Char('q') => {
+      seen.set(true);
+      EventResult::Done
+    }
+    _ => EventResult::Unhandled,
+  });
+  let mut driver = Driver::new(Borders::new().child(hook), Size2D::new(3, 3));
+  assert!(matches!(driver.key(KeyCode::Char('q')), EventResult::Done));
+  assert!(quit.get());
+  assert!(matches!(driver.key(KeyCode::Char('x')), EventResult::Unhandled));
+}
+
+#[test]
+fn unhandled_keys_bubble_up_from_the_focused_widget() {
+  let quit = Rc::new(Cell::new(false));
+  let seen = quit.clone();
+  let root = Hook::child(ColumnView::new().child(CheckBox::new())).on_event(move |_, event, _| match event {
+    AnyEvent::Input(Event::Key(key)) if key.code == KeyCode::Char('q') => {
+      seen.set(true);
+      EventResult::Done
+    }
+    _ => EventResult::Unhandled,
+  });
+  let mut driver = Driver::new(root, Size2D::new(1, 1));
+  assert!(matches!(driver.key(KeyCode::Char('q')), EventResult::Done));
+  assert!(quit.get());
+
+  // keys the focused widget handles stop there
+  quit.set(false);
+  assert!(matches!(driver.key(KeyCode::Char(' ')), EventResult::Done));
+  assert_eq!(driver.lines(), vec!["◉"]);
+  assert!(!quit.get());
+  assert!(matches!(driver.key(KeyCode::Char('x')), EventResult::Unhandled));
+}
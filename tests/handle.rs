@@ -0,0 +1,112 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::thread;
+
+use euclid::default::Size2D;
+
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::widgets::{AnyEvent, Capability, EventResult, Hook, LayoutResult, LayoutSize, RenderResult, Widget};
+use witui::WiTui;
+
+/// Shows the last line it was sent.
+struct Tail(String);
+
+struct Line(String);
+
+impl Widget for Tail {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Custom(message) => match message.downcast_ref::<Line>() {
+        Some(Line(line)) => {
+          self.0 = line.clone();
+          EventResult::Done
+        }
+        None => EventResult::Unhandled,
+      },
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), *avail_size))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write(&self.0);
+    Ok(())
+  }
+
+  fn has_capability(&self, _capability: &Capability) -> bool {
+    false
+  }
+}
+
+#[test]
+fn messages_from_threads_reach_widgets() {
+  let tui = WiTui::root_widget(Tail(String::new()));
+  let handle = tui.handle();
+  let mut driver = Driver::with_tui(tui, Size2D::new(6, 1));
+  assert!(!driver.receive());
+
+  thread::spawn(move || {
+    handle.send(Line("first".into())).unwrap();
+    handle.send(42u32).unwrap();
+    handle.send(Line("second".into())).unwrap();
+  })
+  .join()
+  .unwrap();
+
+  assert!(driver.receive());
+  assert_eq!(driver.lines(), vec!["second"]);
+}
+
+#[test]
+fn updates_run_on_the_ui_thread() {
+  let text = Rc::new(RefCell::new(String::from("idle")));
+  let tui = WiTui::root_widget(text.clone());
+  let handle = tui.handle();
+  let mut driver = Driver::with_tui(tui, Size2D::new(4, 1));
+
+  thread::spawn(move || {
+    handle
+      .update(|tui| {
+        tui.root = Box::new("busy");
+      })
+      .unwrap();
+  })
+  .join()
+  .unwrap();
+
+  assert!(driver.receive());
+  assert_eq!(driver.lines(), vec!["busy"]);
+  assert_eq!(*text.borrow(), "idle");
+}
+
+#[test]
+fn send_fails_once_the_tui_is_gone() {
+  let tui = WiTui::root_widget("");
+  let handle = tui.handle();
+  drop(tui);
+  assert!(handle.send(()).is_err());
+}
+
+#[test]
+fn hooked_widgets_get_each_message_once() {
+  let count = Rc::new(Cell::new(0));
+  let seen = count.clone();
+  let inner = Hook::child("x").on_event(move |_, event, _| match event {
+    AnyEvent::Custom(_) => {
+      seen.set(seen.get() + 1);
+      EventResult::Done
+    }
+    _ => EventResult::Unhandled,
+  });
+  // the outer hook leaves its child to the event loop
+  let tui = WiTui::root_widget(Hook::child(inner));
+  let handle = tui.handle();
+  let mut driver = Driver::with_tui(tui, Size2D::new(1, 1));
+  handle.send(()).unwrap();
+  assert!(driver.receive());
+  assert_eq!(count.get(), 1);
+}
@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+
+use euclid::default::Size2D;
+
+use witui::testing::Driver;
+use witui::widgets::table::{Column, Table};
+use witui::widgets::text::Text;
+use witui::widgets::{Borders, Hook, RowView, Stack, Widget};
+use witui::{Attribute, Color, Style};
+
+#[test]
+fn table() {
+  let table = Table::new()
+    .columns(vec![Column::new("DEVICE"), Column::new("TYPE"), Column::new("STATE")])
+    .data(vec![
+      vec!["wlp8s0", "wifi", "connected"],
+      vec!["enp7s0", "ethernet", "unavailable"],
+    ]);
+  let mut driver = Driver::new(table, Size2D::new(30, 4));
+  assert_eq!(
+    driver.lines(),
+    vec![
+      "DEVICE TYPE     STATE         ",
+      "wlp8s0 wifi     connected     ",
+      "enp7s0 ethernet unavailable   ",
+      "                              ",
+    ]
+  );
+}
+
+#[test]
+fn borders() {
+  let borders = Borders::with_child("Hi").borders_line(Style::new().green().bold());
+  let mut driver = Driver::new(borders, Size2D::new(6, 3));
+  let canvas = driver.canvas();
+  assert_eq!(canvas.lines(), vec!["┌──┐  ", "│Hi│  ", "└──┘  "]);
+  let corner = canvas.cell(0, 0).unwrap();
+  assert_eq!(corner.data(), Some("┌"));
+  assert_eq!(corner.fg(), Some(Color::Green));
+  assert_eq!(corner.bg(), None);
+  assert!(corner.attributes().has(Attribute::Bold));
+  assert_eq!(canvas.cell(4, 0).unwrap().fg(), None);
+}
+
+#[test]
+fn text() {
+  let text = Text::new("Lorem ipsum dolor sit amet");
+  let mut driver = Driver::new(text, Size2D::new(10, 4));
+  assert_eq!(
+    driver.lines(),
+    vec!["Lorem     ", "ipsum     ", "dolor sit ", "amet      "]
+  );
+}
+
+#[test]
+fn row_view() {
+  let row = RowView::new()
+    .child("ab")
+    .child(Style::new().red().child("cd"))
+    .child("ef");
+  let mut driver = Driver::new(row, Size2D::new(8, 1));
+  assert_eq!(driver.lines(), vec!["abcdef  "]);
+  assert_eq!(driver.canvas().dump(), "  0|abcdef  |\n   |  2..4  fg=Red bg=-\n");
+}
+
+#[test]
+fn style_does_not_leak_to_siblings() {
+  let stack = Stack::new()
+    .child(Style::new().green().bold().child("abcd"))
+    .child("xy");
+  let mut driver = Driver::new(stack, Size2D::new(6, 1));
+  assert_eq!(driver.lines(), vec!["xycd  "]);
+  assert_eq!(driver.canvas().dump(), "  0|xycd  |\n   |  2..4  fg=Green bg=- +Bold\n");
+}
+
+#[test]
+fn style_composes_with_parent() {
+  let row = RowView::new()
+    .child("a")
+    .child(Style::new().red().child("b"))
+    .child(Style::new().underlined().child("c"));
+  let root = Style::new().on_blue().bold().child(row);
+  let mut driver = Driver::new(root, Size2D::new(4, 1));
+  assert_eq!(
+    driver.canvas().dump(),
+    concat!(
+      "  0|abc |\n",
+      "   |  0..1  fg=- bg=Blue +Bold\n",
+      "   |  1..2  fg=Red bg=Blue +Bold\n",
+      "   |  2..3  fg=- bg=Blue +Bold +Underlined\n",
+      "   |  3..4  fg=- bg=Blue\n",
+    )
+  );
+}
+
+#[test]
+fn renderer_style_is_scoped_to_the_rendering_widget() {
+  let painted = Hook::child("ab").on_render(|child, ctx| {
+    ctx.renderer().set_foreground(&Color::Red);
+    ctx.renderer().add_attributes(Attribute::Bold.into());
+    ctx.render_child_widget(*ctx.get_frame(), child)
+  });
+  let row = RowView::new().child(painted).child("c");
+  let mut driver = Driver::new(row, Size2D::new(4, 1));
+  assert_eq!(driver.canvas().dump(), "  0|abc |\n   |  0..2  fg=Red bg=- +Bold\n");
+}
+
+#[test]
+fn refcell_renders_the_borrowed_widget() {
+  let cell = RefCell::new(String::from("cell"));
+  assert_eq!(cell.layout(&Size2D::new(10, 1)).unwrap().max, Size2D::new(4, 1));
+  let mut driver = Driver::new(cell, Size2D::new(6, 1));
+  assert_eq!(driver.lines(), vec!["cell  "]);
+  assert!(!driver.widget().has_capability(&witui::widgets::Capability::Selectable));
+}
@@ -0,0 +1,168 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use euclid::default::{Point2D, Size2D};
+
+use witui::backend::Backend;
+use witui::WiTui;
+
+/// Just enough of a terminal to follow what the inline viewport writes: printing with line wrap,
+/// cursor moves, scrolling up and clearing below, the other escape sequences being ignored.
+struct Terminal {
+  rows: Vec<Vec<char>>,
+  cursor: Point2D<usize>,
+  width: usize,
+}
+
+impl Terminal {
+  fn new(width: usize, lines: &[&str]) -> Rc<RefCell<Self>> {
+    let mut rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+    let cursor = Point2D::new(0, rows.iter().rposition(|row| !row.is_empty()).map_or(0, |y| y + 1));
+    rows.iter_mut().for_each(|row| row.resize(width, ' '));
+    Rc::new(RefCell::new(Self { rows, cursor, width }))
+  }
+
+  fn lines(&self) -> Vec<String> {
+    self
+      .rows
+      .iter()
+      .map(|row| row.iter().collect::<String>().trim_end().to_string())
+      .collect()
+  }
+
+  fn new_line(&mut self) {
+    if self.cursor.y + 1 == self.rows.len() {
+      self.scroll_up(1);
+    } else {
+      self.cursor.y += 1;
+    }
+  }
+
+  fn scroll_up(&mut self, rows: usize) {
+    for _ in 0..rows {
+      self.rows.remove(0);
+      self.rows.push(vec![' '; self.width]);
+    }
+  }
+
+  fn feed(&mut self, text: &str) {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+      match c {
+        '\x1b' => {
+          chars.next(); // [
+          let mut params = String::new();
+          let command = loop {
+            match chars.next() {
+              Some(c) if c.is_ascii_digit() || c == ';' || c == '?' => params.push(c),
+              Some(c) => break c,
+              None => return,
+            }
+          };
+          let args: Vec<usize> = params.split(';').filter_map(|arg| arg.parse().ok()).collect();
+          let arg = |idx: usize| args.get(idx).copied().unwrap_or(1);
+          match command {
+            'H' => self.cursor = Point2D::new(arg(1) - 1, arg(0) - 1),
+            'G' => self.cursor.x = arg(0) - 1,
+            'd' => self.cursor.y = arg(0) - 1,
+            'S' => self.scroll_up(arg(0)),
+            'J' => {
+              let (x, y) = (self.cursor.x, self.cursor.y);
+              self.rows[y][x..].iter_mut().for_each(|c| *c = ' ');
+              self.rows[y + 1..]
+                .iter_mut()
+                .for_each(|row| row.iter_mut().for_each(|c| *c = ' '));
+            }
+            _ => {}
+          }
+        }
+        '\r' => self.cursor.x = 0,
+        '\n' => self.new_line(),
+        c => {
+          if self.cursor.x == self.width {
+            self.cursor.x = 0;
+            self.new_line();
+          }
+          self.rows[self.cursor.y][self.cursor.x] = c;
+          self.cursor.x += 1;
+        }
+      }
+    }
+  }
+}
+
+struct TerminalBackend(Rc<RefCell<Terminal>>);
+
+impl Backend for TerminalBackend {
+  fn size(&self) -> std::io::Result<Size2D<usize>> {
+    let terminal = self.0.borrow();
+    Ok(Size2D::new(terminal.width, terminal.rows.len()))
+  }
+
+  fn enable_raw_mode(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn disable_raw_mode(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+
+  fn cursor_position(&mut self) -> std::io::Result<Point2D<usize>> {
+    Ok(self.0.borrow().cursor)
+  }
+
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<()> {
+    self.0.borrow_mut().feed(std::str::from_utf8(buf).unwrap());
+    Ok(())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[test]
+fn viewport_is_redrawn_in_place_below_the_prompt() {
+  let terminal = Terminal::new(12, &["$ deploy", "", "", "", ""]);
+  let mut tui = WiTui::root_widget("step 1/2")
+    .backend(TerminalBackend(terminal.clone()))
+    .inline(2);
+  tui.print().unwrap();
+  assert_eq!(terminal.borrow().lines(), vec!["$ deploy", "step 1/2", "", "", ""]);
+
+  tui.root = Box::new("done");
+  tui.print().unwrap();
+  assert_eq!(terminal.borrow().lines(), vec!["$ deploy", "done", "", "", ""]);
+}
+
+#[test]
+fn printed_lines_scroll_above_the_viewport() {
+  let terminal = Terminal::new(12, &["a", "b", "$ deploy", ""]);
+  let mut tui = WiTui::root_widget("50%")
+    .backend(TerminalBackend(terminal.clone()))
+    .inline(2);
+  tui.print().unwrap();
+  // scrolled up for the viewport to fit
+  assert_eq!(terminal.borrow().lines(), vec!["b", "$ deploy", "50%", ""]);
+
+  tui.println("uploaded");
+  tui.println("restarted");
+  tui.root = Box::new("100%");
+  tui.print().unwrap();
+  assert_eq!(terminal.borrow().lines(), vec!["uploaded", "restarted", "100%", ""]);
+}
+
+#[test]
+fn last_frame_stays_once_dropped() {
+  let terminal = Terminal::new(12, &["$ deploy", "", "", "", ""]);
+  let mut tui = WiTui::root_widget("done")
+    .backend(TerminalBackend(terminal.clone()))
+    .inline(2);
+  tui.print().unwrap();
+  tui.println("bye");
+  drop(tui);
+  let terminal = terminal.borrow();
+  assert_eq!(terminal.lines(), vec!["$ deploy", "bye", "done", "", ""]);
+  // the prompt comes back below the viewport
+  assert_eq!(terminal.cursor, Point2D::new(0, 4));
+}
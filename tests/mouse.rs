@@ -0,0 +1,529 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crossterm::event::MouseButton;
+use euclid::default::{Point2D, Rect, SideOffsets2D, Size2D, Vector2D};
+
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::widgets::table::{Column, Table};
+use witui::widgets::{
+  AnyEvent, Capability, CheckBox, ColumnView, EventResult, LayoutResult, LayoutSize, MouseGesture, Padding,
+  RenderResult, RowView, Stack, Widget,
+};
+use witui::{Event, KeyCode, MouseEventKind};
+
+type Log = Rc<RefCell<Vec<(&'static str, Seen)>>>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Seen {
+  Press(u16, u16, Size2D<usize>),
+  Gesture(MouseGesture),
+}
+
+fn press(name: &'static str, x: u16, y: u16, width: usize, height: usize) -> (&'static str, Seen) {
+  (name, Seen::Press(x, y, Size2D::new(width, height)))
+}
+
+fn gesture(name: &'static str, gesture: MouseGesture) -> (&'static str, Seen) {
+  (name, Seen::Gesture(gesture))
+}
+
+fn presses(log: &Log) -> Vec<(&'static str, Seen)> {
+  let log = log.borrow();
+  log
+    .iter()
+    .filter(|(_, seen)| matches!(seen, Seen::Press(..)))
+    .cloned()
+    .collect()
+}
+
+/// Gestures logged, hovering aside.
+fn gestures(log: &Log) -> Vec<(&'static str, Seen)> {
+  let log = log.borrow();
+  let hover = |seen: &Seen| match seen {
+    Seen::Gesture(gesture) => matches!(gesture, MouseGesture::HoverEnter | MouseGesture::HoverLeave),
+    Seen::Press(..) => true,
+  };
+  log.iter().filter(|(_, seen)| !hover(seen)).cloned().collect()
+}
+
+/// Fills a line of its frame and logs the mouse presses and gestures it gets.
+struct Probe {
+  name: &'static str,
+  handles: bool,
+  log: Log,
+}
+
+impl Probe {
+  fn new(name: &'static str, handles: bool, log: &Log) -> Self {
+    Self {
+      name,
+      handles,
+      log: log.clone(),
+    }
+  }
+}
+
+impl Widget for Probe {
+  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Input(Event::Mouse(mouse)) if matches!(mouse.kind, MouseEventKind::Down(_)) => {
+        let seen = Seen::Press(mouse.column, mouse.row, *size);
+        self.log.borrow_mut().push((self.name, seen));
+      }
+      AnyEvent::Mouse(gesture) => self.log.borrow_mut().push((self.name, Seen::Gesture(*gesture))),
+      _ => return EventResult::Unhandled,
+    }
+    if self.handles {
+      EventResult::Done
+    } else {
+      EventResult::Unhandled
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), Size2D::new(avail_size.width, 1)))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write(&self.name.repeat(ctx.get_frame().width()));
+    Ok(())
+  }
+
+  fn has_capability(&self, _capability: &Capability) -> bool {
+    false
+  }
+}
+
+/// Probe wrapping a child, to see events bubbling up to it.
+struct Parent<Child> {
+  probe: Probe,
+  child: Child,
+}
+
+impl<Child: Widget> Widget for Parent<Child> {
+  fn event(&mut self, event: &AnyEvent, size: &Size2D<usize>) -> EventResult {
+    self.probe.event(event, size)
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    self.child.layout(avail_size)
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.render_child_widget(*ctx.get_frame(), &self.child)
+  }
+
+  fn has_capability(&self, _capability: &Capability) -> bool {
+    false
+  }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    visitor(&self.child)
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    visitor(&mut self.child)
+  }
+}
+
+/// Zero-sized widget taking the clicks.
+struct Button;
+
+impl Widget for Button {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Input(Event::Mouse(mouse)) if matches!(mouse.kind, MouseEventKind::Down(_)) => EventResult::Done,
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, _avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), Size2D::new(1, 1)))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write("b");
+    Ok(())
+  }
+}
+
+/// Two widgets drawn at both ends of the frame.
+struct Apart<A, B>(A, B);
+
+impl<A: Widget, B: Widget> Widget for Apart<A, B> {
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(2, 1), Size2D::new(avail_size.width, 1)))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = *ctx.get_frame();
+    let cell = |x: usize| Rect::new(Point2D::new(frame.min_x() + x, frame.min_y()), Size2D::new(1, 1));
+    ctx.render_child_widget(cell(0), &self.0)?;
+    ctx.render_child_widget(cell(frame.width() - 1), &self.1)
+  }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    visitor(&self.0);
+    visitor(&self.1);
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    visitor(&mut self.0);
+    visitor(&mut self.1);
+  }
+}
+
+/// Newtype drawing its checkbox one column to the right, at the same address as the checkbox.
+struct Indented(CheckBox);
+
+impl Widget for Indented {
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let mut layout = self.0.layout(avail_size)?;
+    layout.min.width += 1;
+    layout.max.width += 1;
+    Ok(layout)
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    let frame = ctx.get_frame().inner_rect(SideOffsets2D::new(0, 0, 0, 1));
+    ctx.render_child_widget(frame, &self.0)
+  }
+
+  fn visit_children(&self, visitor: &mut dyn FnMut(&dyn Widget)) {
+    visitor(&self.0)
+  }
+
+  fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(&mut dyn Widget)) {
+    visitor(&mut self.0)
+  }
+}
+
+#[test]
+fn click_goes_to_widget_under_pointer() {
+  let root = RowView::new().child(CheckBox::new()).child(CheckBox::new());
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  assert!(matches!(driver.click(1, 0), EventResult::Done));
+  assert_eq!(driver.lines(), vec!["○◉"]);
+  assert!(matches!(driver.click(0, 0), EventResult::Done));
+  assert_eq!(driver.lines(), vec!["◉◉"]);
+}
+
+#[test]
+fn zero_sized_widgets_are_hit() {
+  let root = Padding::default().left(1).child(Button);
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  assert_eq!(driver.lines(), vec![" b"]);
+  assert!(matches!(driver.click(0, 0), EventResult::Unhandled));
+  assert!(matches!(driver.click(1, 0), EventResult::Done));
+
+  // siblings sharing their address are told apart by their order
+  let mut driver = Driver::new(Apart(Button, Button), Size2D::new(3, 1));
+  assert_eq!(driver.lines(), vec!["b b"]);
+  assert!(matches!(driver.click(0, 0), EventResult::Done));
+  assert!(matches!(driver.click(1, 0), EventResult::Unhandled));
+  assert!(matches!(driver.click(2, 0), EventResult::Done));
+}
+
+#[test]
+fn newtype_is_told_apart_from_its_child() {
+  let root = Padding::default().child(Indented(CheckBox::new()));
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  assert_eq!(driver.lines(), vec![" ○"]);
+  assert!(matches!(driver.click(0, 0), EventResult::Unhandled));
+  assert_eq!(driver.lines(), vec![" ○"]);
+  assert!(matches!(driver.click(1, 0), EventResult::Done));
+  assert_eq!(driver.lines(), vec![" ◉"]);
+}
+
+#[test]
+fn coordinates_are_relative_to_the_widget() {
+  let log = Log::default();
+  let root = ColumnView::new()
+    .child(Probe::new("a", true, &log))
+    .child(Padding::default().left(2).child(Probe::new("b", true, &log)));
+  let mut driver = Driver::new(root, Size2D::new(5, 2));
+  assert_eq!(driver.lines(), vec!["aaaaa", "  bbb"]);
+
+  driver.click(3, 0);
+  driver.click(3, 1);
+  assert_eq!(presses(&log), vec![press("a", 3, 0, 5, 1), press("b", 1, 0, 3, 1)]);
+}
+
+#[test]
+fn unhandled_events_bubble_up() {
+  let log = Log::default();
+  let root = Parent {
+    probe: Probe::new("p", true, &log),
+    child: Padding::all(1).child(Probe::new("c", false, &log)),
+  };
+  let mut driver = Driver::new(root, Size2D::new(4, 3));
+  assert!(matches!(driver.click(2, 1), EventResult::Done));
+  assert_eq!(presses(&log), vec![press("c", 1, 0, 2, 1), press("p", 2, 1, 4, 3)]);
+
+  // the padding around the child hits the parent directly
+  log.borrow_mut().clear();
+  driver.click(0, 0);
+  assert_eq!(presses(&log), vec![press("p", 0, 0, 4, 3)]);
+}
+
+#[test]
+fn handled_events_stop_bubbling() {
+  let log = Log::default();
+  let root = Parent {
+    probe: Probe::new("p", true, &log),
+    child: Probe::new("c", true, &log),
+  };
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  driver.click(1, 0);
+  assert_eq!(presses(&log), vec![press("c", 1, 0, 2, 1)]);
+}
+
+#[test]
+fn topmost_stacked_widget_is_hit() {
+  let log = Log::default();
+  let root = Stack::new()
+    .child(Probe::new("a", true, &log))
+    .child(Probe::new("b", true, &log));
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  assert_eq!(driver.lines(), vec!["bb"]);
+  driver.click(0, 0);
+  assert_eq!(presses(&log), vec![press("b", 0, 0, 2, 1)]);
+}
+
+#[test]
+fn containers_leave_unhandled_events() {
+  let root = ColumnView::new().child(RowView::new().child("text"));
+  let mut driver = Driver::new(root, Size2D::new(4, 2));
+  assert!(matches!(driver.click(1, 0), EventResult::Unhandled));
+  assert!(matches!(driver.click(1, 1), EventResult::Unhandled));
+}
+
+#[test]
+fn click_and_double_click() {
+  let log = Log::default();
+  let mut driver = Driver::new(Probe::new("a", true, &log), Size2D::new(3, 1));
+  driver.click(1, 0);
+  driver.click(1, 0);
+  driver.click(1, 0);
+  let click = MouseGesture::Click {
+    button: MouseButton::Left,
+    position: Point2D::new(1, 0),
+  };
+  let double = MouseGesture::DoubleClick {
+    button: MouseButton::Left,
+    position: Point2D::new(1, 0),
+  };
+  assert_eq!(
+    gestures(&log),
+    vec![
+      gesture("a", click),
+      gesture("a", click),
+      gesture("a", double),
+      gesture("a", click)
+    ]
+  );
+
+  // clicks on different cells are not a double-click
+  log.borrow_mut().clear();
+  driver.click(2, 0);
+  assert_eq!(
+    gestures(&log),
+    vec![gesture(
+      "a",
+      MouseGesture::Click {
+        button: MouseButton::Left,
+        position: Point2D::new(2, 0)
+      }
+    )]
+  );
+}
+
+#[test]
+fn release_over_another_widget_is_no_click() {
+  let log = Log::default();
+  let root = ColumnView::new()
+    .child(Probe::new("a", true, &log))
+    .child(Probe::new("b", true, &log));
+  let mut driver = Driver::new(root, Size2D::new(3, 2));
+  driver.mouse(MouseEventKind::Down(MouseButton::Left), 0, 0);
+  driver.mouse(MouseEventKind::Up(MouseButton::Left), 0, 1);
+  assert_eq!(gestures(&log), vec![]);
+}
+
+#[test]
+fn drag_goes_to_the_widget_taking_it() {
+  let log = Log::default();
+  let root = ColumnView::new()
+    .child(Padding::default().left(1).child(Probe::new("a", true, &log)))
+    .child(Probe::new("b", true, &log));
+  let mut driver = Driver::new(root, Size2D::new(4, 2));
+  driver.mouse(MouseEventKind::Down(MouseButton::Left), 2, 0);
+  driver.mouse(MouseEventKind::Drag(MouseButton::Left), 3, 1);
+  driver.mouse(MouseEventKind::Drag(MouseButton::Left), 0, 1);
+  driver.mouse(MouseEventKind::Up(MouseButton::Left), 0, 1);
+
+  let button = MouseButton::Left;
+  let origin = Point2D::new(1, 0);
+  assert_eq!(
+    gestures(&log),
+    vec![
+      gesture("a", MouseGesture::DragStart { button, origin }),
+      gesture(
+        "a",
+        MouseGesture::DragMove {
+          button,
+          origin,
+          offset: Vector2D::new(1, 1)
+        }
+      ),
+      gesture(
+        "a",
+        MouseGesture::DragMove {
+          button,
+          origin,
+          offset: Vector2D::new(-2, 1)
+        }
+      ),
+      gesture(
+        "a",
+        MouseGesture::DragEnd {
+          button,
+          origin,
+          offset: Vector2D::new(-2, 1)
+        }
+      ),
+    ]
+  );
+}
+
+#[test]
+fn unhandled_drag_start_bubbles_up() {
+  let log = Log::default();
+  let root = Parent {
+    probe: Probe::new("p", true, &log),
+    child: Padding::all(1).child(Probe::new("c", false, &log)),
+  };
+  let mut driver = Driver::new(root, Size2D::new(4, 3));
+  assert!(matches!(
+    driver.drag(MouseButton::Right, (1, 1), (3, 2)),
+    EventResult::Done
+  ));
+
+  let button = MouseButton::Right;
+  let offset = Vector2D::new(2, 1);
+  assert_eq!(
+    gestures(&log),
+    vec![
+      gesture(
+        "c",
+        MouseGesture::DragStart {
+          button,
+          origin: Point2D::new(0, 0)
+        }
+      ),
+      gesture(
+        "p",
+        MouseGesture::DragStart {
+          button,
+          origin: Point2D::new(1, 1)
+        }
+      ),
+      gesture(
+        "p",
+        MouseGesture::DragMove {
+          button,
+          origin: Point2D::new(1, 1),
+          offset
+        }
+      ),
+      gesture(
+        "p",
+        MouseGesture::DragEnd {
+          button,
+          origin: Point2D::new(1, 1),
+          offset
+        }
+      ),
+    ]
+  );
+}
+
+#[test]
+fn hover_enter_and_leave() {
+  let log = Log::default();
+  let root = ColumnView::new()
+    .child(Probe::new("a", true, &log))
+    .child(Probe::new("b", true, &log));
+  let mut driver = Driver::new(root, Size2D::new(3, 2));
+  driver.mouse(MouseEventKind::Moved, 0, 0);
+  driver.mouse(MouseEventKind::Moved, 1, 0);
+  driver.mouse(MouseEventKind::Moved, 1, 1);
+  assert_eq!(
+    *log.borrow(),
+    vec![
+      gesture("a", MouseGesture::HoverEnter),
+      gesture("a", MouseGesture::HoverLeave),
+      gesture("b", MouseGesture::HoverEnter),
+    ]
+  );
+}
+
+#[test]
+fn wheel_scrolls_widget_under_pointer() {
+  let log = Log::default();
+  let root = ColumnView::new()
+    .child(Probe::new("a", true, &log))
+    .child(Probe::new("b", true, &log));
+  let mut driver = Driver::new(root, Size2D::new(3, 2));
+  assert!(matches!(
+    driver.mouse(MouseEventKind::ScrollDown, 2, 1),
+    EventResult::Done
+  ));
+  driver.mouse(MouseEventKind::ScrollUp, 2, 1);
+  let position = Point2D::new(2, 0);
+  assert_eq!(
+    gestures(&log),
+    vec![
+      gesture("b", MouseGesture::Wheel { delta: 1, position }),
+      gesture("b", MouseGesture::Wheel { delta: -1, position }),
+    ]
+  );
+}
+
+#[test]
+fn table_columns_get_the_clicks_on_their_heading() {
+  let table = Table::new()
+    .columns(vec![Column::new("NAME"), Column::new("TYPE")])
+    .data(vec![vec!["a", "wifi"]]);
+  let mut driver = Driver::new(table, Size2D::new(9, 2));
+  assert_eq!(driver.lines(), vec!["NAME TYPE", "a    wifi"]);
+  // the middle button shrinks the column to a single cell
+  assert!(matches!(
+    driver.click_with(MouseButton::Middle, 6, 0),
+    EventResult::Done
+  ));
+  assert_eq!(driver.lines(), vec!["NAME …   ", "a    …   "]);
+  assert!(matches!(
+    driver.click_with(MouseButton::Middle, 1, 1),
+    EventResult::Unhandled
+  ));
+}
+
+#[test]
+fn table_cells_get_the_focus_and_the_clicks() {
+  let table = Table::new()
+    .columns(vec![Column::new("A"), Column::new("B")])
+    .data(vec![vec![CheckBox::new(), CheckBox::new()]]);
+  let mut driver = Driver::new(table, Size2D::new(3, 2));
+  assert_eq!(driver.lines(), vec!["A B", "○ ○"]);
+  driver.click(2, 1);
+  assert_eq!(driver.lines(), vec!["A B", "○ ◉"]);
+  // the first cell has the focus
+  driver.key(KeyCode::Char(' '));
+  assert_eq!(driver.lines(), vec!["A B", "◉ ◉"]);
+  driver.key(KeyCode::Tab);
+  driver.key(KeyCode::Char(' '));
+  assert_eq!(driver.lines(), vec!["A B", "◉ ○"]);
+}
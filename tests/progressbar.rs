@@ -0,0 +1,87 @@
+use euclid::default::Size2D;
+
+use witui::testing::{Driver, Headless};
+use witui::widgets::{AnyEvent, Expanded, ProgressBar, RowView, Widget};
+use witui::{Attribute, Color, Style};
+
+#[test]
+fn bar_is_filled_to_the_eighth_of_a_cell() {
+  assert_eq!(
+    Driver::new(ProgressBar::new().ratio(0.55), Size2D::new(4, 1)).lines(),
+    vec!["██▎ "]
+  );
+  assert_eq!(
+    Driver::new(ProgressBar::new().value(3.0, 4.0), Size2D::new(4, 1)).lines(),
+    vec!["███ "]
+  );
+  assert_eq!(
+    Driver::new(ProgressBar::new().value(9.0, 4.0), Size2D::new(4, 1)).lines(),
+    vec!["████"]
+  );
+  assert_eq!(
+    Driver::new(ProgressBar::new().value(1.0, 0.0), Size2D::new(4, 1)).lines(),
+    vec!["    "]
+  );
+  let bar = ProgressBar::new().ratio(0.5).fill('=').empty('-');
+  assert_eq!(Driver::new(bar, Size2D::new(4, 1)).lines(), vec!["==--"]);
+}
+
+#[test]
+fn vertical_bar_fills_from_the_bottom() {
+  let bar = ProgressBar::new().vertical().ratio(0.5);
+  assert_eq!(Driver::new(bar, Size2D::new(2, 3)).lines(), vec!["  ", "▄▄", "██"]);
+}
+
+#[test]
+fn label_is_inverted_over_the_bar() {
+  let gauge = ProgressBar::gauge(0.5).bar_style(Style::new().fg(Color::Green));
+  let mut driver = Driver::new(gauge, Size2D::new(10, 1));
+  assert_eq!(driver.lines(), vec!["███50%    "]);
+  let canvas = driver.canvas();
+  let (five, percent) = (canvas.cell(3, 0).unwrap(), canvas.cell(5, 0).unwrap());
+  assert!(five.attributes().has(Attribute::Reverse));
+  assert_eq!(five.fg(), Some(Color::Green));
+  assert!(!percent.attributes().has(Attribute::Reverse));
+  assert_eq!(percent.fg(), None);
+
+  let bar = ProgressBar::new().ratio(0.25).label("copying");
+  assert_eq!(
+    Driver::new(bar, Size2D::new(9, 3)).lines(),
+    vec!["██▎      ", "█copying ", "██▎      "]
+  );
+}
+
+/// Lines of the bar after the ticks.
+fn tick(driver: &mut Driver<Headless<ProgressBar>>, ticks: usize) -> Vec<String> {
+  for _ in 0..ticks {
+    driver.widget_mut().event(&AnyEvent::Tick, &Size2D::new(8, 1));
+  }
+  driver.render();
+  driver.lines()
+}
+
+#[test]
+fn indeterminate_bar_bounces_at_each_tick() {
+  let mut driver = Driver::new(ProgressBar::new().indeterminate().percentage(), Size2D::new(8, 1));
+  assert_eq!(tick(&mut driver, 0), vec!["██      "]);
+  assert_eq!(tick(&mut driver, 2), vec![" ██     "]);
+  assert_eq!(tick(&mut driver, 10), vec!["      ██"]);
+  // on the way back
+  assert_eq!(tick(&mut driver, 1), vec!["     ▌█▌"]);
+  assert_eq!(tick(&mut driver, 11), vec!["██      "]);
+}
+
+#[test]
+fn bar_stretches_when_expanded() {
+  let row = RowView::new()
+    .child("[")
+    .child(Expanded::child(ProgressBar::new().ratio(1.0)))
+    .child("]");
+  assert_eq!(Driver::new(row, Size2D::new(10, 1)).lines(), vec!["[████████]"]);
+  // its default length otherwise
+  let row = RowView::new().child(ProgressBar::new().ratio(1.0)).child("|");
+  assert_eq!(
+    Driver::new(row, Size2D::new(24, 1)).lines(),
+    vec![format!("{}|   ", "█".repeat(20))]
+  );
+}
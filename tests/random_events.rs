@@ -0,0 +1,260 @@
+//! Random input thrown at every built-in widget, which must neither panic nor fail to handle it.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent};
+use crossterm::style::Stylize;
+use euclid::default::{Point2D, Size2D, Vector2D};
+
+use witui::testing::{Driver, DriverTarget};
+use witui::tree::walk_mut;
+use witui::widgets::table::Column;
+use witui::widgets::*;
+use witui::{Event, KeyCode, KeyModifiers, MouseEventKind, Style};
+
+/// Xorshift generator, seeded for the failures to be reproducible.
+struct Random(u64);
+
+impl Random {
+  fn next(&mut self) -> u64 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    self.0
+  }
+
+  fn below(&mut self, max: u64) -> u64 {
+    self.next() % max
+  }
+
+  fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+    items[self.below(items.len() as u64) as usize]
+  }
+}
+
+const KEYS: &[KeyCode] = &[
+  KeyCode::Char('a'),
+  KeyCode::Char(' '),
+  KeyCode::Char('é'),
+  KeyCode::Char('字'),
+  KeyCode::Enter,
+  KeyCode::Backspace,
+  KeyCode::Delete,
+  KeyCode::Left,
+  KeyCode::Right,
+  KeyCode::Up,
+  KeyCode::Down,
+  KeyCode::Home,
+  KeyCode::End,
+  KeyCode::PageUp,
+  KeyCode::PageDown,
+  KeyCode::Tab,
+  KeyCode::BackTab,
+  KeyCode::Esc,
+  KeyCode::F(1),
+];
+
+const BUTTONS: &[MouseButton] = &[MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+
+const MODIFIERS: &[KeyModifiers] = &[
+  KeyModifiers::NONE,
+  KeyModifiers::SHIFT,
+  KeyModifiers::CONTROL,
+  KeyModifiers::ALT,
+];
+
+fn input(random: &mut Random) -> Event {
+  let modifiers = random.pick(MODIFIERS);
+  match random.below(10) {
+    0..=4 => Event::Key(KeyEvent::new(random.pick(KEYS), modifiers)),
+    5..=8 => {
+      let button = random.pick(BUTTONS);
+      let kind = random.pick(&[
+        MouseEventKind::Down(button),
+        MouseEventKind::Up(button),
+        MouseEventKind::Drag(button),
+        MouseEventKind::Moved,
+        MouseEventKind::ScrollDown,
+        MouseEventKind::ScrollUp,
+      ]);
+      Event::Mouse(MouseEvent {
+        kind,
+        column: random.below(24) as u16,
+        row: random.below(8) as u16,
+        modifiers,
+      })
+    }
+    _ => Event::Resize(1 + random.below(24) as u16, 1 + random.below(8) as u16),
+  }
+}
+
+/// Any event, for direct delivery to the widgets.
+fn any_event(random: &mut Random) -> AnyEvent {
+  let point = Point2D::new(random.below(24) as usize, random.below(8) as usize);
+  let offset = Vector2D::new(random.below(9) as isize - 4, random.below(9) as isize - 4);
+  let button = random.pick(BUTTONS);
+  match random.below(13) {
+    0 => AnyEvent::FocusIn,
+    1 => AnyEvent::FocusOut,
+    2 => AnyEvent::Tick,
+    3 => AnyEvent::Custom(Box::new(random.next())),
+    4 => AnyEvent::Mouse(MouseGesture::Click {
+      button,
+      position: point,
+    }),
+    5 => AnyEvent::Mouse(MouseGesture::DoubleClick {
+      button,
+      position: point,
+    }),
+    6 => AnyEvent::Mouse(MouseGesture::DragStart { button, origin: point }),
+    7 => AnyEvent::Mouse(MouseGesture::DragMove {
+      button,
+      origin: point,
+      offset,
+    }),
+    8 => AnyEvent::Mouse(MouseGesture::DragEnd {
+      button,
+      origin: point,
+      offset,
+    }),
+    9 => AnyEvent::Mouse(MouseGesture::HoverEnter),
+    10 => AnyEvent::Mouse(MouseGesture::HoverLeave),
+    11 => AnyEvent::Mouse(MouseGesture::Wheel {
+      delta: random.below(7) as isize - 3,
+      position: point,
+    }),
+    _ => AnyEvent::Input(input(random)),
+  }
+}
+
+fn widgets() -> Vec<(&'static str, Box<dyn Widget>)> {
+  vec![
+    ("str", Box::new("text")),
+    ("String", Box::new(String::from("wide 字 text"))),
+    ("char", Box::new('x')),
+    ("u32", Box::new(42u32)),
+    ("usize", Box::new(7usize)),
+    ("unit", Box::new(())),
+    ("StyledContent", Box::new("styled".red())),
+    ("Rc", Box::new(Rc::new("shared"))),
+    ("RefCell", Box::new(RefCell::new(CheckBox::new()))),
+    ("FillChar", Box::new(FillChar::new('.'))),
+    ("Button", Box::new(Button::child("OK"))),
+    ("CheckBox", Box::new(CheckBox::new())),
+    ("TextInput", Box::new(TextInput::new())),
+    ("Text", Box::new(Text::new("some longer text\nover lines"))),
+    ("Min", Box::new(Min::zero().child("min"))),
+    ("Leak", Box::new(Leak::child("leaking"))),
+    ("Repeat", Box::new(Repeat::child('~'))),
+    ("Expanded", Box::new(Expanded::child(CheckBox::new()))),
+    ("Flexible", Box::new(Flexible::loose(1).child("flex"))),
+    ("Padding", Box::new(Padding::all(1).child(TextInput::new()))),
+    ("Align", Box::new(Align::center(CheckBox::new()))),
+    ("Styled", Box::new(Style::default().child(TextInput::new()))),
+    (
+      "Borders",
+      Box::new(Borders::new().borders_line(Style::default()).child("boxed")),
+    ),
+    ("Hook", Box::new(Hook::child(CheckBox::new()))),
+    ("Id", Box::new(Id::new("id").child(TextInput::new()))),
+    (
+      "RowView",
+      Box::new(
+        RowView::new()
+          .child(CheckBox::new())
+          .child("row")
+          .child(TextInput::new()),
+      ),
+    ),
+    (
+      "ColumnView",
+      Box::new(
+        ColumnView::new()
+          .child(CheckBox::new())
+          .child("column")
+          .child(TextInput::new()),
+      ),
+    ),
+    (
+      "Stack",
+      Box::new(Stack::new().child(FillChar::new('#')).child(CheckBox::new())),
+    ),
+    ("empty Stack", Box::new(Stack::<Vec<Box<dyn Widget>>>::new())),
+    (
+      "ScrollView",
+      Box::new(
+        ScrollView::new()
+          .child(Text::new("scrolled\nfar\nbelow\nthe\nframe"))
+          .offset(Point2D::new(3, 9)),
+      ),
+    ),
+    ("ProgressBar", Box::new(ProgressBar::gauge(0.42).label("loading"))),
+    (
+      "Vertical ProgressBar",
+      Box::new(ProgressBar::new().vertical().indeterminate()),
+    ),
+    ("Scrollbar", Box::new(Scrollbar::horizontal().position(40, 7, 12))),
+    (
+      "Tabs",
+      Box::new(
+        Tabs::new()
+          .tab("Form", ColumnView::new().child(TextInput::new()).child(CheckBox::new()))
+          .tab("A rather long title", Text::new("page"))
+          .tab(Text::new("Last"), ())
+          .active(1),
+      ),
+    ),
+    (
+      "Scrolled",
+      Box::new(
+        Scrollbar::vertical()
+          .attach(ScrollView::new().child(Text::new("a\nb\nc\nd\ne\nf")))
+          .scrollbar(Scrollbar::horizontal()),
+      ),
+    ),
+    (
+      "Table",
+      Box::new(
+        Table::new()
+          .columns(vec![Column::new("NAME"), Column::new("STATE")])
+          .data(vec![vec!["one", "up"], vec!["two", "down"]]),
+      ),
+    ),
+  ]
+}
+
+#[test]
+fn random_input_through_every_widget() {
+  for (seed, (name, widget)) in widgets().into_iter().enumerate() {
+    let mut random = Random(0x9E37_79B9_7F4A_7C15 ^ seed as u64);
+    // shows which widget failed, along with the panic
+    eprintln!("widget: {}", name);
+    let mut driver = Driver::new(widget, Size2D::new(16, 4));
+    for _ in 0..500 {
+      let event = input(&mut random);
+      let target = driver.target_mut();
+      target.dispatch(event);
+      // frames too small for the widget fail to render, but must not panic
+      let _ = target.render();
+    }
+  }
+}
+
+#[test]
+fn random_events_to_every_widget_in_the_tree() {
+  for (seed, (name, widget)) in widgets().into_iter().enumerate() {
+    let mut random = Random(0xD1B5_4A32_D192_ED03 ^ seed as u64);
+    eprintln!("widget: {}", name);
+    let mut driver = Driver::new(widget, Size2D::new(16, 4));
+    for _ in 0..200 {
+      let event = any_event(&mut random);
+      let size = Size2D::new(random.below(20) as usize, random.below(6) as usize);
+      walk_mut(driver.widget_mut().as_mut(), &mut |widget, _| {
+        widget.event(&event, &size);
+        let _ = widget.layout(&size);
+      });
+      let _ = driver.target_mut().render();
+    }
+  }
+}
@@ -0,0 +1,225 @@
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use euclid::default::Size2D;
+
+use witui::record::{Recorder, ReplayError};
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::widgets::{
+  AnyEvent, CheckBox, ColumnView, EventResult, LayoutResult, LayoutSize, MouseGesture, RenderResult, TextInput, Widget,
+};
+use witui::{KeyCode, WiTui};
+#[cfg(feature = "tokio")]
+use {crossterm::event::KeyEvent, witui::backend::HeadlessBackend, witui::Event};
+
+/// Writer kept around to read what was recorded.
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+
+impl Shared {
+  fn text(&self) -> String {
+    String::from_utf8(self.0.borrow().clone()).unwrap()
+  }
+}
+
+impl Write for Shared {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.borrow_mut().extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+fn form() -> WiTui {
+  WiTui::root_widget(ColumnView::new().child(TextInput::new()).child(CheckBox::new())).alternate(true)
+}
+
+/// Record a session on the form, returns the cast and the input log.
+fn record() -> (String, String) {
+  let (cast, input) = (Shared::default(), Shared::default());
+  let tui = form().record(Recorder::new(cast.clone(), input.clone()));
+  let mut driver = Driver::with_tui(tui, Size2D::new(8, 2));
+  driver.type_str("hi");
+  driver.key(KeyCode::Tab);
+  driver.key(KeyCode::Char(' '));
+  driver.resize(6, 2);
+  driver.click(0, 0);
+  drop(driver);
+  (cast.text(), input.text())
+}
+
+#[test]
+fn session_is_recorded_as_asciicast() {
+  let (cast, input) = record();
+  let mut cast = cast.lines();
+  assert!(cast
+    .next()
+    .unwrap()
+    .starts_with("{\"version\": 2, \"width\": 8, \"height\": 2, "));
+  let outputs: Vec<&str> = cast.collect();
+  assert!(outputs.iter().all(|line| line.starts_with('[')));
+  assert!(outputs.iter().any(|line| line.ends_with(", \"r\", \"6x2\"]")));
+  assert!(outputs
+    .iter()
+    .any(|line| line.contains(", \"o\", ") && line.contains("hi")));
+
+  let events: Vec<&str> = input.lines().map(|line| line.split_once(", ").unwrap().1).collect();
+  assert_eq!(
+    events,
+    vec![
+      "\"key\", \"h\", 0]",
+      "\"key\", \"i\", 0]",
+      "\"key\", \"Tab\", 0]",
+      "\"key\", \" \", 0]",
+      "\"resize\", 6, 2]",
+      "\"mouse\", \"down\", \"left\", 0, 0, 0]",
+      "\"mouse\", \"up\", \"left\", 0, 0, 0]",
+    ]
+  );
+}
+
+#[test]
+fn replay_matches_the_recording() {
+  let (cast, input) = record();
+  form().replay(cast.as_bytes(), input.as_bytes()).unwrap();
+}
+
+#[test]
+fn replay_reports_the_first_diverging_output() {
+  let (cast, input) = record();
+  // the check box starts marked this time
+  let tui = WiTui::root_widget(
+    ColumnView::new()
+      .child(TextInput::new())
+      .child(CheckBox::new().marked()),
+  );
+  match tui.alternate(true).replay(cast.as_bytes(), input.as_bytes()) {
+    Err(ReplayError::Mismatch {
+      index,
+      expected,
+      actual,
+    }) => {
+      assert!(index > 0);
+      assert_ne!(expected, actual);
+    }
+    result => panic!("unexpected replay result: {:?}", result),
+  }
+}
+
+#[test]
+fn malformed_lines_are_reported() {
+  let (cast, _) = record();
+  let result = form().replay(cast.as_bytes(), "[0.1, \"key\"]\n".as_bytes());
+  assert!(matches!(result, Err(ReplayError::Malformed(line)) if line == "[0.1, \"key\"]"));
+}
+
+/// Counts the double clicks.
+struct DoubleClicks(usize);
+
+impl Widget for DoubleClicks {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Mouse(MouseGesture::DoubleClick { .. }) => {
+        self.0 += 1;
+        EventResult::Done
+      }
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), *avail_size))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write(&self.0.to_string());
+    Ok(())
+  }
+}
+
+#[test]
+fn replay_tells_double_clicks_apart_on_the_recorded_clock() {
+  let session = || WiTui::root_widget(DoubleClicks(0)).alternate(true);
+  let (cast, input) = (Shared::default(), Shared::default());
+  let tui = session().record(Recorder::new(cast.clone(), input.clone()));
+  let mut driver = Driver::with_tui(tui, Size2D::new(4, 1));
+  driver.click(0, 0);
+  // too slow for a double click
+  thread::sleep(Duration::from_millis(600));
+  driver.click(0, 0);
+  assert_eq!(driver.lines()[0].trim_end(), "0");
+  drop(driver);
+
+  let (cast, input) = (cast.text(), input.text());
+  session().replay(cast.as_bytes(), input.as_bytes()).unwrap();
+}
+
+/// Counts the ticks.
+#[cfg(feature = "tokio")]
+struct Ticks(usize);
+
+#[cfg(feature = "tokio")]
+impl Widget for Ticks {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Tick => {
+        self.0 += 1;
+        EventResult::Done
+      }
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), *avail_size))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write(&self.0.to_string());
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn replay_runs_the_ticks_on_the_recorded_clock() {
+  use futures::{stream, StreamExt};
+  use witui::KeyModifiers;
+
+  let session = || {
+    WiTui::root_widget(ColumnView::new().child(Ticks(0)).child(CheckBox::new()))
+      .alternate(true)
+      .tick_rate(Duration::from_millis(100))
+  };
+  let (cast, input) = (Shared::default(), Shared::default());
+  let mut tui = session()
+    .record(Recorder::new(cast.clone(), input.clone()))
+    .backend(HeadlessBackend::new(Size2D::new(4, 2)));
+  // keys halfway between the ticks
+  let keys = stream::iter(vec![
+    (150, KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE)),
+    (200, KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+  ]);
+  let keys = keys
+    .then(|(delay, key)| async move {
+      tokio::time::sleep(Duration::from_millis(delay)).await;
+      Ok(Event::Key(key))
+    })
+    .boxed_local();
+  tui.run_with_input(keys).await.unwrap();
+  drop(tui);
+
+  let (cast, input) = (cast.text(), input.text());
+  session().replay(cast.as_bytes(), input.as_bytes()).unwrap();
+  // the ticks make the frames
+  let faster = session().tick_rate(Duration::from_millis(60));
+  let result = faster.replay(cast.as_bytes(), input.as_bytes());
+  assert!(matches!(result, Err(ReplayError::Mismatch { .. })));
+}
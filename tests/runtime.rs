@@ -0,0 +1,184 @@
+#![cfg(feature = "tokio")]
+
+use std::thread;
+use std::time::Duration;
+
+use crossterm::event::KeyEvent;
+use euclid::default::Size2D;
+use futures::stream;
+
+use witui::backend::HeadlessBackend;
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::widgets::{
+  AnyEvent, Capability, CheckBox, EventResult, LayoutError, LayoutResult, LayoutSize, RenderError, RenderResult, Widget,
+};
+use witui::{Event, KeyCode, KeyModifiers, WiTui};
+
+/// Shows the last line it was sent.
+struct Tail(String);
+
+struct Line(&'static str);
+
+impl Widget for Tail {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Custom(message) => match message.downcast_ref::<Line>() {
+        Some(Line(line)) => {
+          self.0 = line.to_string();
+          EventResult::Done
+        }
+        None => EventResult::Unhandled,
+      },
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), *avail_size))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write(&self.0);
+    Ok(())
+  }
+
+  fn has_capability(&self, _capability: &Capability) -> bool {
+    false
+  }
+}
+
+fn key(code: KeyCode) -> crossterm::Result<Event> {
+  Ok(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+}
+
+/// Input that quits with Ctrl-C after the delay.
+fn quit_after(delay: Duration) -> impl futures::Stream<Item = crossterm::Result<Event>> + Unpin {
+  stream::once(Box::pin(async move {
+    tokio::time::sleep(delay).await;
+    Ok(Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)))
+  }))
+}
+
+#[tokio::test]
+async fn input_stream_drives_the_loop() {
+  let mut driver = Driver::with_tui(WiTui::root_widget(CheckBox::new()), Size2D::new(1, 1));
+  let input = stream::iter(vec![
+    key(KeyCode::Char(' ')),
+    key(KeyCode::Char(' ')),
+    key(KeyCode::Enter),
+  ]);
+  driver.tui_mut().run_with_input(input).await.unwrap();
+  assert_eq!(driver.lines(), vec!["◉"]);
+}
+
+#[tokio::test]
+async fn futures_and_streams_reach_widgets() {
+  let mut driver = Driver::with_tui(WiTui::root_widget(Tail(String::new())), Size2D::new(6, 1));
+  let tui = driver.tui_mut();
+  tui.future(async {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    Line("future")
+  });
+  tui.run_with_input(quit_after(Duration::from_millis(50))).await.unwrap();
+  assert_eq!(driver.lines(), vec!["future"]);
+
+  let tui = driver.tui_mut();
+  tui.stream(stream::iter(vec![Line("first"), Line("second")]));
+  tui.run_with_input(quit_after(Duration::from_millis(50))).await.unwrap();
+  assert_eq!(driver.lines(), vec!["second"]);
+}
+
+#[tokio::test]
+async fn future_updates_mutate_the_tree() {
+  let mut driver = Driver::with_tui(WiTui::root_widget("wait"), Size2D::new(4, 1));
+  let tui = driver.tui_mut();
+  tui.future_update(async {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    |tui: &mut WiTui| tui.root = Box::new("done")
+  });
+  tui.run_with_input(quit_after(Duration::from_millis(50))).await.unwrap();
+  assert_eq!(driver.lines(), vec!["done"]);
+}
+
+#[tokio::test]
+async fn quit_ends_the_loop_after_a_last_frame() {
+  let mut driver = Driver::with_tui(WiTui::root_widget("wait"), Size2D::new(4, 1));
+  let tui = driver.tui_mut();
+  tui.future_update(async {
+    |tui: &mut WiTui| {
+      tui.root = Box::new("done");
+      tui.quit();
+    }
+  });
+  tui.run_with_input(stream::pending()).await.unwrap();
+  assert_eq!(driver.lines(), vec!["done"]);
+}
+
+#[tokio::test]
+async fn handles_wake_the_loop_up() {
+  let tui = WiTui::root_widget("wait");
+  let handle = tui.handle();
+  let mut driver = Driver::with_tui(tui, Size2D::new(4, 1));
+  thread::spawn(move || {
+    thread::sleep(Duration::from_millis(10));
+    handle
+      .update(|tui: &mut WiTui| {
+        tui.root = Box::new("done");
+        tui.quit();
+      })
+      .unwrap();
+  });
+  // no tick nor timer to wake the loop up otherwise
+  let run = driver.tui_mut().run_with_input(stream::pending());
+  tokio::time::timeout(Duration::from_secs(5), run)
+    .await
+    .expect("the loop slept through the update")
+    .unwrap();
+  assert_eq!(driver.lines(), vec!["done"]);
+}
+
+#[tokio::test]
+async fn input_errors_end_the_loop() {
+  let mut driver = Driver::with_tui(WiTui::root_widget(CheckBox::new()), Size2D::new(1, 1));
+  let input = stream::iter(vec![
+    key(KeyCode::Char(' ')),
+    Err(std::io::Error::other("input gone")),
+    key(KeyCode::Char(' ')),
+  ]);
+  let result = driver.tui_mut().run_with_input(input).await;
+  assert!(matches!(result, Err(RenderError::Io(error)) if error.to_string() == "input gone"));
+  assert_eq!(driver.lines(), vec!["◉"]);
+}
+
+/// Fails to render every frame.
+struct Broken;
+
+impl Widget for Broken {
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(Size2D::new(1, 1), *avail_size))
+  }
+
+  fn render(&self, _ctx: &RenderCtx) -> RenderResult {
+    Err(RenderError::Layout(LayoutError::InsufficientSpace))
+  }
+}
+
+/// Broken WiTui quitting from a thread once its loop runs.
+fn quitting_broken() -> WiTui {
+  let tui = WiTui::root_widget(Broken)
+    .backend(HeadlessBackend::new(Size2D::new(4, 1)))
+    .alternate(true);
+  let handle = tui.handle();
+  thread::spawn(move || {
+    thread::sleep(Duration::from_millis(10));
+    handle.update(|tui: &mut WiTui| tui.quit()).unwrap();
+  });
+  tui
+}
+
+#[tokio::test]
+async fn render_errors_end_neither_loop() {
+  quitting_broken().run_loop().unwrap();
+  quitting_broken().run_with_input(stream::pending()).await.unwrap();
+}
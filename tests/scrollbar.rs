@@ -0,0 +1,105 @@
+use crossterm::event::MouseButton;
+use euclid::default::Size2D;
+
+use witui::testing::{numbered_rows, Driver};
+use witui::widgets::{Borders, EventResult, Orientation, ScrollPosition, ScrollRequest, ScrollView, Scrollbar, Widget};
+use witui::{Attribute, MouseEventKind, Style};
+
+#[test]
+fn thumb_is_placed_to_the_eighth_of_a_cell() {
+  let bar = Scrollbar::vertical().position(100, 25, 0);
+  assert_eq!(Driver::new(bar, Size2D::new(1, 4)).lines(), vec!["█", "│", "│", "│"]);
+
+  // from 3/8 to 11/8 of the track
+  let mut driver = Driver::new(Scrollbar::vertical().position(100, 25, 10), Size2D::new(1, 4));
+  assert_eq!(driver.lines(), vec!["▅", "▅", "│", "│"]);
+  // the thumb ending in a cell is drawn reversed, over the block of the track
+  let canvas = driver.canvas();
+  assert!(!canvas.cell(0, 0).unwrap().attributes().has(Attribute::Reverse));
+  assert!(canvas.cell(0, 1).unwrap().attributes().has(Attribute::Reverse));
+}
+
+#[test]
+fn horizontal_thumb_ends_with_the_track() {
+  let bar = Scrollbar::horizontal().position(10, 5, 5);
+  assert_eq!(Driver::new(bar, Size2D::new(4, 1)).lines(), vec!["──██"]);
+  let bar = Scrollbar::horizontal().position(10, 5, 2).track('·').thumb('=');
+  assert_eq!(Driver::new(bar, Size2D::new(4, 1)).lines(), vec!["▊=▊·"]);
+  // all the content showing
+  let bar = Scrollbar::horizontal().position(3, 5, 0);
+  assert_eq!(Driver::new(bar, Size2D::new(4, 1)).lines(), vec!["████"]);
+}
+
+fn requested(result: EventResult) -> Option<ScrollRequest> {
+  match result {
+    EventResult::Message(message) => message.downcast::<ScrollRequest>().ok().map(|request| *request),
+    _ => None,
+  }
+}
+
+#[test]
+fn clicking_the_track_requests_a_page() {
+  let mut driver = Driver::new(Scrollbar::vertical().position(100, 25, 30), Size2D::new(1, 4));
+  let down = ScrollRequest {
+    orientation: Orientation::Vertical,
+    offset: 55,
+  };
+  assert_eq!(requested(driver.click(0, 3)), Some(down));
+  assert_eq!(requested(driver.click(0, 0)).map(|request| request.offset), Some(5));
+  // on the thumb
+  assert!(matches!(driver.click(0, 1), EventResult::Done));
+}
+
+#[test]
+fn attached_scrollbar_scrolls_its_child() {
+  let view = Scrollbar::vertical().attach(ScrollView::new().child(numbered_rows(10, "abc")));
+  let mut driver = Driver::new(view, Size2D::new(5, 3));
+  assert_eq!(driver.lines(), vec!["0abc█", "1abc│", "2abc│"]);
+  driver.click(4, 2);
+  assert_eq!(driver.lines(), vec!["3abc▁", "4abc▁", "5abc│"]);
+  // dragging the thumb down one cell of its 2 cells travel
+  driver.drag(MouseButton::Left, (4, 0), (4, 1));
+  assert_eq!(driver.widget().child.offset.y, 6);
+  driver.drag(MouseButton::Left, (4, 1), (4, 2));
+  assert_eq!(driver.lines(), vec!["7abc│", "8abc│", "9abc█"]);
+}
+
+#[test]
+fn borders_draw_a_side_as_a_scrollbar() {
+  let borders = Borders::new()
+    .borders_line(Style::new())
+    .scrollbar_right(Scrollbar::vertical())
+    .child(ScrollView::new().child(numbered_rows(10, "abc")));
+  let mut driver = Driver::new(borders, Size2D::new(6, 5));
+  assert_eq!(driver.lines(), vec!["┌────┐", "│0abc█", "│1abc│", "│2abc│", "└────┘"]);
+  driver.mouse(MouseEventKind::ScrollDown, 5, 2);
+  assert_eq!(driver.lines(), vec!["┌────┐", "│1abc▆", "│2abc▆", "│3abc│", "└────┘"]);
+}
+
+#[test]
+fn borders_hug_a_child_smaller_than_their_frame() {
+  let borders = Borders::new()
+    .borders_line(Style::new())
+    .scrollbar_right(Scrollbar::vertical())
+    .child(ScrollView::new().child(numbered_rows(10, "abc")));
+  let mut driver = Driver::new(borders, Size2D::new(10, 5));
+  assert_eq!(
+    driver.lines(),
+    vec!["┌────┐    ", "│0abc█    ", "│1abc│    ", "│2abc│    ", "└────┘    "]
+  );
+  let position = driver
+    .widget()
+    .scroll_position(Orientation::Vertical, &Size2D::new(10, 5));
+  assert_eq!(position, Some(ScrollPosition::new(10, 3, 0)));
+  let position = driver
+    .widget()
+    .scroll_position(Orientation::Horizontal, &Size2D::new(10, 5));
+  assert_eq!(position, Some(ScrollPosition::new(4, 4, 0)));
+  // past the right side the borders leave empty
+  assert!(matches!(driver.click(9, 3), EventResult::Unhandled));
+  driver.click(5, 3);
+  assert_eq!(
+    driver.lines(),
+    vec!["┌────┐    ", "│3abc▁    ", "│4abc▁    ", "│5abc│    ", "└────┘    "]
+  );
+}
@@ -0,0 +1,178 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use euclid::default::{Point2D, Size2D};
+
+use witui::render::RenderCtx;
+use witui::testing::{numbered_rows, Driver};
+use witui::widgets::{
+  CheckBox, ColumnView, Expanded, FillChar, LayoutResult, LayoutSize, Padding, RenderResult, ScrollView, Widget,
+};
+use witui::{Attribute, Color, KeyCode, MouseEventKind, Style};
+
+#[test]
+fn keys_move_the_viewport_within_the_child() {
+  let mut driver = Driver::new(ScrollView::new().child(numbered_rows(10, "abcdef")), Size2D::new(4, 3));
+  assert_eq!(driver.lines(), vec!["0abc", "1abc", "2abc"]);
+  driver.key(KeyCode::Down);
+  assert_eq!(driver.lines(), vec!["1abc", "2abc", "3abc"]);
+  driver.key(KeyCode::Right);
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.lines(), vec!["bcde", "bcde", "bcde"]);
+  // stops at the right edge of the child
+  driver.key(KeyCode::Right);
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.widget().offset, Point2D::new(3, 1));
+  assert_eq!(driver.lines(), vec!["cdef", "cdef", "cdef"]);
+
+  driver.key(KeyCode::PageDown);
+  assert_eq!(driver.widget().offset.y, 3);
+  driver.key(KeyCode::End);
+  assert_eq!(driver.widget().offset.y, 7);
+  driver.key(KeyCode::PageUp);
+  assert_eq!(driver.widget().offset.y, 5);
+  driver.key(KeyCode::Home);
+  driver.key(KeyCode::Left);
+  assert_eq!(driver.widget().offset, Point2D::new(2, 0));
+}
+
+#[test]
+fn mouse_wheel_scrolls() {
+  let mut driver = Driver::new(ScrollView::new().child(numbered_rows(10, "abcdef")), Size2D::new(4, 3));
+  driver.mouse(MouseEventKind::ScrollDown, 1, 1);
+  driver.mouse(MouseEventKind::ScrollDown, 1, 1);
+  assert_eq!(driver.lines(), vec!["2abc", "3abc", "4abc"]);
+  driver.mouse(MouseEventKind::ScrollUp, 1, 1);
+  assert_eq!(driver.lines(), vec!["1abc", "2abc", "3abc"]);
+}
+
+#[test]
+fn offset_is_clamped_when_rendering() {
+  let view = ScrollView::new()
+    .child(numbered_rows(10, "abcdef"))
+    .offset(Point2D::new(100, 100));
+  let mut driver = Driver::new(view, Size2D::new(4, 3));
+  assert_eq!(driver.lines(), vec!["cdef", "cdef", "cdef"]);
+  // a child smaller than the viewport does not scroll
+  let mut driver = Driver::new(ScrollView::new().child("hi"), Size2D::new(4, 3));
+  driver.key(KeyCode::Down);
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.widget().offset, Point2D::zero());
+  assert_eq!(driver.lines(), vec!["hi  ", "    ", "    "]);
+}
+
+#[test]
+fn widgets_in_the_child_are_hit_where_they_show() {
+  let column = (0..5)
+    .fold(ColumnView::new(), |column, row| column.child(row.to_string()))
+    .child(CheckBox::new());
+  let view = ScrollView::new().child(column).offset(Point2D::new(0, 4));
+  let mut driver = Driver::new(view, Size2D::new(3, 2));
+  assert_eq!(driver.lines(), vec!["4  ", "○  "]);
+  driver.click(0, 1);
+  assert_eq!(driver.lines(), vec!["4  ", "◉  "]);
+}
+
+#[test]
+fn wide_graphemes_cut_by_the_edges_are_blanked() {
+  let view = ScrollView::new().child("日本語").offset(Point2D::new(1, 0));
+  let mut driver = Driver::new(view, Size2D::new(3, 1));
+  assert_eq!(driver.lines(), vec![" 本"]);
+}
+
+#[test]
+fn styles_are_inherited_and_kept() {
+  let view = Style::new()
+    .bold()
+    .child(ScrollView::new().child(Style::new().fg(Color::Red).child("ab")));
+  let mut driver = Driver::new(view, Size2D::new(3, 1));
+  let canvas = driver.canvas();
+  let cell = canvas.cell(0, 0).unwrap();
+  assert_eq!(cell.data(), Some("a"));
+  assert_eq!(cell.fg(), Some(Color::Red));
+  assert!(cell.attributes().has(Attribute::Bold));
+}
+
+#[test]
+fn keys_left_by_a_focused_descendant_scroll() {
+  let column = (0..10).fold(ColumnView::new().child(CheckBox::new()), |column, row| {
+    column.child(row.to_string())
+  });
+  let mut driver = Driver::new(ScrollView::new().child(column), Size2D::new(3, 3));
+  assert_eq!(driver.lines(), vec!["○  ", "0  ", "1  "]);
+  driver.key(KeyCode::Down);
+  assert_eq!(driver.widget().offset.y, 1);
+  assert_eq!(driver.lines(), vec!["0  ", "1  ", "2  "]);
+  // the check box still gets its keys
+  driver.key(KeyCode::Up);
+  driver.key(KeyCode::Char(' '));
+  assert_eq!(driver.lines(), vec!["◉  ", "0  ", "1  "]);
+}
+
+#[test]
+fn focused_descendant_is_scrolled_into_view() {
+  let column = (0..10)
+    .fold(ColumnView::new().child(CheckBox::new()), |column, row| {
+      column.child(row.to_string())
+    })
+    .child(CheckBox::new());
+  let mut driver = Driver::new(ScrollView::new().child(column), Size2D::new(3, 3));
+  driver.key(KeyCode::Tab);
+  assert_eq!(driver.widget().offset.y, 9);
+  assert_eq!(driver.lines(), vec!["8  ", "9  ", "○  "]);
+  driver.key(KeyCode::Char(' '));
+  assert_eq!(driver.lines(), vec!["8  ", "9  ", "◉  "]);
+  driver.key(KeyCode::Tab);
+  assert_eq!(driver.widget().offset.y, 0);
+}
+
+#[test]
+fn children_filling_their_space_fill_the_viewport() {
+  let mut driver = Driver::new(ScrollView::new().child(FillChar::new('x')), Size2D::new(3, 2));
+  assert_eq!(driver.lines(), vec!["xxx", "xxx"]);
+  driver.key(KeyCode::Down);
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.widget().offset, Point2D::zero());
+  let view = ScrollView::new().child(FillChar::new('x'));
+  assert_eq!(witui::render_to_string(&view, 4).unwrap(), "xxxx\n");
+
+  let expanded = ScrollView::new().child(Expanded::child(FillChar::new('x')));
+  assert_eq!(Driver::new(expanded, Size2D::new(3, 2)).lines(), vec!["xxx", "xxx"]);
+  let padded = ScrollView::new().child(Padding::all(1).child(FillChar::new('x')));
+  assert_eq!(
+    Driver::new(padded, Size2D::new(3, 3)).lines(),
+    vec!["   ", " x ", "   "]
+  );
+}
+
+/// Counts its renders.
+struct Renders(Rc<Cell<usize>>);
+
+impl Widget for Renders {
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    Ok(LayoutSize::min_max(
+      Size2D::new(1, 1),
+      Size2D::new(1, 1).min(*avail_size),
+    ))
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    self.0.set(self.0.get() + 1);
+    ctx.renderer().write("r");
+    Ok(())
+  }
+}
+
+#[test]
+fn moving_the_focus_renders_a_single_frame() {
+  let renders = Rc::new(Cell::new(0));
+  let column = ColumnView::new()
+    .child(Renders(renders.clone()))
+    .child(CheckBox::new())
+    .child(CheckBox::new());
+  let mut driver = Driver::new(ScrollView::new().child(column), Size2D::new(1, 2));
+  let before = renders.get();
+  driver.key(KeyCode::Tab);
+  assert_eq!(driver.widget().offset.y, 1);
+  assert_eq!(renders.get(), before + 1);
+}
@@ -0,0 +1,126 @@
+use euclid::default::Size2D;
+
+use witui::testing::Driver;
+use witui::widgets::{ColumnView, Tabs, Text, TextInput, Widget};
+use witui::{Attribute, Color, KeyCode, MouseEventKind};
+
+fn tabs() -> Tabs {
+  Tabs::new()
+    .tab("One", "first")
+    .tab("Two", "second")
+    .tab("Three", "third")
+}
+
+#[test]
+fn bar_shows_the_titles_over_the_active_page() {
+  let mut driver = Driver::new(tabs(), Size2D::new(20, 2));
+  assert_eq!(driver.lines(), vec!["─┤One├┤Two├┤Three├──", "first               "]);
+  let canvas = driver.canvas();
+  let (active, inactive, line) = (
+    canvas.cell(2, 0).unwrap(),
+    canvas.cell(7, 0).unwrap(),
+    canvas.cell(1, 0).unwrap(),
+  );
+  assert_eq!(active.bg(), Some(Color::DarkGreen));
+  assert!(active.attributes().has(Attribute::Bold));
+  assert_eq!(inactive.bg(), None);
+  assert_eq!(line.fg(), Some(Color::DarkGreen));
+}
+
+#[test]
+fn keys_and_clicks_switch_pages() {
+  let mut driver = Driver::new(tabs(), Size2D::new(20, 2));
+  driver.click(7, 0);
+  assert_eq!(driver.widget().get_active(), 1);
+  assert_eq!(driver.lines()[1], "second              ");
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.lines()[1], "third               ");
+  // stays on the last tab
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.widget().get_active(), 2);
+  driver.key(KeyCode::Home);
+  assert_eq!(driver.widget().get_active(), 0);
+  driver.key(KeyCode::End);
+  driver.key(KeyCode::Left);
+  assert_eq!(driver.widget().get_active(), 1);
+  driver.mouse(MouseEventKind::ScrollUp, 3, 0);
+  assert_eq!(driver.widget().get_active(), 0);
+  // neither the line nor the page switch tabs
+  driver.click(19, 0);
+  driver.click(2, 1);
+  assert_eq!(driver.widget().get_active(), 0);
+}
+
+#[test]
+fn bar_scrolls_to_the_active_title() {
+  let mut driver = Driver::new(tabs(), Size2D::new(12, 1));
+  assert_eq!(driver.lines(), vec!["─┤One├┤Two├▶"]);
+  driver.key(KeyCode::End);
+  assert_eq!(driver.lines(), vec!["◀┤Three├────"]);
+  // the arrows go to the next hidden tab
+  driver.click(0, 0);
+  assert_eq!(driver.widget().get_active(), 1);
+  assert_eq!(driver.lines(), vec!["─┤One├┤Two├▶"]);
+  driver.click(11, 0);
+  assert_eq!(driver.widget().get_active(), 2);
+}
+
+#[test]
+fn long_title_is_truncated() {
+  let tabs = Tabs::new().tab("A very long title", "page");
+  assert_eq!(
+    Driver::new(tabs, Size2D::new(10, 2)).lines(),
+    vec!["─┤A ver…├─", "page      "]
+  );
+}
+
+#[test]
+fn only_the_active_page_is_laid_out() {
+  let tabs = Tabs::new().tab("Lines", Text::new("a\nb\nc")).tab("Line", "x");
+  assert_eq!(tabs.layout(&Size2D::new(10, 10)).unwrap().max, Size2D::new(10, 4));
+  let tabs = tabs.active(1);
+  assert_eq!(tabs.layout(&Size2D::new(10, 10)).unwrap().max, Size2D::new(10, 2));
+
+  let page = |name: &str| ColumnView::new().child(name.to_string()).child(TextInput::new());
+  let tabs = Tabs::new().tab("A", page("a")).tab("B", page("b"));
+  let mut driver = Driver::new(ColumnView::new().child(tabs), Size2D::new(8, 3));
+  assert_eq!(driver.lines(), vec!["─┤A├┤B├─", "a       ", "        "]);
+  // the bar has the focus first, then the input of the active page
+  assert!(driver
+    .canvas()
+    .cell(2, 0)
+    .unwrap()
+    .attributes()
+    .has(Attribute::Underlined));
+  driver.key(KeyCode::Tab);
+  driver.type_str("hi");
+  driver.key(KeyCode::BackTab);
+  driver.key(KeyCode::Right);
+  assert_eq!(driver.lines(), vec!["─┤A├┤B├─", "b       ", "        "]);
+  driver.key(KeyCode::Left);
+  assert_eq!(driver.lines(), vec!["─┤A├┤B├─", "a       ", "hi      "]);
+}
+
+#[test]
+fn switching_pages_takes_the_focus_from_the_page_left() {
+  let page = |name: &str| ColumnView::new().child(name.to_string()).child(TextInput::new());
+  let tabs = Tabs::new().tab("A", page("a")).tab("B", page("b"));
+  let mut driver = Driver::new(ColumnView::new().child(tabs), Size2D::new(8, 3));
+  let cursor = |driver: &mut Driver<_>| driver.canvas().cell(0, 2).unwrap().attributes().has(Attribute::Reverse);
+  driver.key(KeyCode::Tab);
+  assert!(cursor(&mut driver));
+  driver.click(5, 0);
+  driver.type_str("xy");
+  // the bar has the focus back, the input of the new page neither gets the keys nor draws the cursor
+  assert_eq!(driver.lines(), vec!["─┤A├┤B├─", "b       ", "        "]);
+  assert!(!cursor(&mut driver));
+  assert!(driver
+    .canvas()
+    .cell(5, 0)
+    .unwrap()
+    .attributes()
+    .has(Attribute::Underlined));
+  driver.key(KeyCode::Left);
+  assert_eq!(driver.lines(), vec!["─┤A├┤B├─", "a       ", "        "]);
+  assert!(!cursor(&mut driver));
+}
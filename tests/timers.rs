@@ -0,0 +1,138 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use euclid::default::Size2D;
+
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::timer::Timers;
+use witui::widgets::{AnyEvent, Capability, EventResult, LayoutError, LayoutResult, LayoutSize, RenderResult, Widget};
+use witui::WiTui;
+
+const SECOND: Duration = Duration::from_secs(1);
+
+fn counter() -> (Rc<Cell<usize>>, impl FnMut() + 'static) {
+  let count = Rc::new(Cell::new(0));
+  let inner = count.clone();
+  (count, move || inner.set(inner.get() + 1))
+}
+
+#[test]
+fn one_shot_fires_once() {
+  let timers = Timers::new();
+  let start = Instant::now();
+  let (count, callback) = counter();
+  timers.after(SECOND, callback);
+  assert_eq!(timers.fire_due(start), 0);
+  assert_eq!(timers.fire_due(start + 2 * SECOND), 1);
+  assert_eq!(timers.fire_due(start + 4 * SECOND), 0);
+  assert_eq!(count.get(), 1);
+  assert!(timers.is_empty());
+}
+
+#[test]
+fn repeating_fires_until_cancelled() {
+  let timers = Timers::new();
+  let start = Instant::now();
+  let (count, callback) = counter();
+  let id = timers.every(SECOND, callback);
+  assert!(timers.next_deadline().unwrap() > start);
+  timers.fire_due(start + 2 * SECOND);
+  timers.fire_due(start + 3 * SECOND);
+  // missed intervals are skipped
+  timers.fire_due(start + 10 * SECOND);
+  assert_eq!(count.get(), 3);
+  assert!(timers.next_deadline().unwrap() > start + 10 * SECOND);
+
+  timers.cancel(id);
+  timers.fire_due(start + 20 * SECOND);
+  assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn callbacks_can_schedule_and_cancel() {
+  let timers = Timers::new();
+  let start = Instant::now();
+  let fired = Rc::new(RefCell::new(Vec::new()));
+
+  let (inner_timers, inner_fired) = (timers.clone(), fired.clone());
+  let id = Rc::new(Cell::new(None));
+  let inner_id = id.clone();
+  id.set(Some(timers.every(SECOND, move || {
+    inner_fired.borrow_mut().push("every");
+    inner_timers.cancel(inner_id.get().unwrap());
+    let fired = inner_fired.clone();
+    inner_timers.after(5 * SECOND, move || fired.borrow_mut().push("after"));
+  })));
+
+  timers.fire_due(start + 2 * SECOND);
+  assert_eq!(*fired.borrow(), vec!["every"]);
+  timers.fire_due(start + 10 * SECOND);
+  assert_eq!(*fired.borrow(), vec!["every", "after"]);
+  assert!(timers.is_empty());
+}
+
+/// Renders how many ticks it got, in two cells.
+struct Ticks(usize);
+
+impl Widget for Ticks {
+  fn event(&mut self, event: &AnyEvent, _size: &Size2D<usize>) -> EventResult {
+    match event {
+      AnyEvent::Tick => {
+        self.0 += 1;
+        EventResult::Done
+      }
+      _ => EventResult::Unhandled,
+    }
+  }
+
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    let size = Size2D::new(2, 1);
+    if avail_size.contains(size) {
+      Ok(LayoutSize::min_max(size, size))
+    } else {
+      Err(LayoutError::InsufficientSpace)
+    }
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.renderer().write(&self.0.to_string());
+    Ok(())
+  }
+
+  fn has_capability(&self, _capability: &Capability) -> bool {
+    false
+  }
+}
+
+#[test]
+fn tick_reaches_every_widget() {
+  let root = witui::widgets::RowView::new().child(Ticks(0)).child(Ticks(10));
+  let tui = WiTui::root_widget(root).tick_rate(SECOND);
+  let mut driver = Driver::with_tui(tui, Size2D::new(4, 1));
+  assert_eq!(driver.lines(), vec!["0 10"]);
+
+  let start = Instant::now();
+  assert!(!driver.advance_to(start));
+  assert!(driver.advance_to(start + 2 * SECOND));
+  assert_eq!(driver.lines(), vec!["1 11"]);
+  assert!(driver.advance_to(start + 4 * SECOND));
+  assert_eq!(driver.lines(), vec!["2 12"]);
+}
+
+#[test]
+fn timer_callback_rerenders() {
+  let text = Rc::new(RefCell::new(String::from("wait")));
+  let tui = WiTui::root_widget(text.clone());
+  let timers = tui.timers();
+  let mut driver = Driver::with_tui(tui, Size2D::new(4, 1));
+  assert_eq!(driver.lines(), vec!["wait"]);
+
+  let start = Instant::now();
+  timers.after(SECOND, move || *text.borrow_mut() = String::from("done"));
+  assert!(!driver.advance_to(start));
+  assert_eq!(driver.lines(), vec!["wait"]);
+  assert!(driver.advance_to(start + 2 * SECOND));
+  assert_eq!(driver.lines(), vec!["done"]);
+}
@@ -0,0 +1,104 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use euclid::default::Size2D;
+
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::tree::{walk, walk_mut};
+use witui::widgets::{
+  Align, AnyEvent, Capability, CheckBox, EventResult, Hook, Id, LayoutResult, MouseGesture, Padding, RenderResult,
+  Stack, Widget, WidgetId,
+};
+use witui::{KeyCode, WiTui};
+
+fn tree() -> impl Widget {
+  Stack::new()
+    .child(Padding::default().child(Id::new("a").child(Align::top_left(Id::new("b").child(String::from("x"))))))
+    .child(Hook::child(Id::new("c").child(CheckBox::new())))
+}
+
+#[test]
+fn walk_is_depth_first_parents_first() {
+  let mut ids = Vec::new();
+  walk(&tree(), &mut |widget, path| {
+    if let Some(WidgetId::Name(name)) = widget.id() {
+      ids.push((name.to_string(), path.to_vec()));
+    }
+  });
+  let expected = vec![
+    (String::from("a"), vec![0, 0]),
+    (String::from("b"), vec![0, 0, 0, 0]),
+    (String::from("c"), vec![1, 0]),
+  ];
+  assert_eq!(ids, expected);
+}
+
+#[test]
+fn walk_mut_reaches_every_widget() {
+  let mut root = tree();
+  let mut count = 0;
+  walk_mut(&mut root, &mut |widget, _| {
+    count += 1;
+    if let Some(text) = widget.as_any_mut().and_then(|any| any.downcast_mut::<String>()) {
+      text.push('y');
+    }
+  });
+  // stack, padding, id, align, id, string, hook, id, checkbox
+  assert_eq!(count, 9);
+  let mut driver = Driver::new(root, Size2D::new(2, 1));
+  // the checkbox is stacked over the text
+  assert_eq!(driver.lines(), vec!["○y"]);
+}
+
+#[test]
+fn hook_gets_what_the_child_left_unhandled() {
+  let unhandled = Rc::new(Cell::new(0));
+  let count = unhandled.clone();
+  let hook = Hook::child(CheckBox::new()).on_event(move |_, event, _| match event {
+    AnyEvent::Mouse(MouseGesture::Click { .. }) => {
+      count.set(count.get() + 1);
+      EventResult::Done
+    }
+    _ => EventResult::Unhandled,
+  });
+  let mut driver = Driver::new(hook, Size2D::new(1, 1));
+  // the press toggles the checkbox, which leaves the click gesture unhandled
+  driver.click(0, 0);
+  assert_eq!(unhandled.get(), 1);
+  assert_eq!(driver.lines(), vec!["◉"]);
+}
+
+/// Wrapper implementing only what `single_child` doesn't forward.
+struct Framed<Child>(Child);
+
+impl<Child: Widget> Widget for Framed<Child> {
+  fn layout(&self, avail_size: &Size2D<usize>) -> LayoutResult {
+    self.0.layout(avail_size)
+  }
+
+  fn render(&self, ctx: &RenderCtx) -> RenderResult {
+    ctx.render_child_widget(*ctx.get_frame(), &self.0)
+  }
+
+  fn single_child(&self) -> Option<&dyn Widget> {
+    Some(&self.0)
+  }
+
+  fn single_child_mut(&mut self) -> Option<&mut dyn Widget> {
+    Some(&mut self.0)
+  }
+}
+
+#[test]
+fn single_child_wrappers_forward_to_their_child() {
+  let framed = Framed(Id::new("box").child(CheckBox::new()));
+  assert!(framed.has_capability(&Capability::Selectable));
+  let mut paths = Vec::new();
+  walk(&framed, &mut |_, path| paths.push(path.to_vec()));
+  assert_eq!(paths, vec![vec![], vec![0], vec![0, 0]]);
+
+  let mut driver = Driver::with_tui(WiTui::root_widget(framed), Size2D::new(1, 1));
+  driver.key(KeyCode::Char(' '));
+  assert!(driver.tui_mut().find_mut::<CheckBox>("box").unwrap().is_marked());
+}
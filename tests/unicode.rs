@@ -0,0 +1,76 @@
+use euclid::default::Size2D;
+
+use witui::canvas::Symbol;
+use witui::render::RenderCtx;
+use witui::testing::Driver;
+use witui::util::{display_width, truncate};
+use witui::widgets::table::{Column, Table};
+use witui::widgets::RowView;
+
+#[test]
+fn widths() {
+  assert_eq!(display_width("abc"), 3);
+  assert_eq!(display_width("日本"), 4);
+  assert_eq!(display_width("e\u{301}te\u{301}"), 3);
+  assert_eq!(display_width("…"), 1);
+  assert_eq!(display_width("🦀"), 2);
+
+  assert_eq!(truncate("日本語", 5), ("日本", 4));
+  assert_eq!(truncate("ae\u{301}b", 2), ("ae\u{301}", 2));
+  assert_eq!(truncate("abc", 10), ("abc", 3));
+}
+
+#[test]
+fn wide_graphemes_take_two_cells() {
+  let mut driver = Driver::new("日本🦀e\u{301}!", Size2D::new(8, 1));
+  let canvas = driver.canvas();
+  assert_eq!(canvas.lines(), vec!["日本🦀e\u{301}!"]);
+  assert_eq!(canvas.cell(0, 0).unwrap().data(), Some("日"));
+  assert_eq!(canvas.cell(1, 0).unwrap().symbol(), &Symbol::Continuation);
+  assert_eq!(canvas.cell(4, 0).unwrap().data(), Some("🦀"));
+  assert_eq!(canvas.cell(6, 0).unwrap().data(), Some("e\u{301}"));
+  assert_eq!(canvas.cell(7, 0).unwrap().data(), Some("!"));
+}
+
+#[test]
+fn truncation_with_ellipsis() {
+  // a wide grapheme never gets split in half, the leftover cell stays empty
+  let mut driver = Driver::new("日本語", Size2D::new(4, 1));
+  assert_eq!(driver.lines(), vec!["日… "]);
+
+  let mut driver = Driver::new("ab日本語", Size2D::new(5, 1));
+  assert_eq!(driver.lines(), vec!["ab日…"]);
+}
+
+#[test]
+fn overwritten_wide_halves_are_cleared() {
+  let ctx = RenderCtx::headless(Size2D::new(6, 1));
+  ctx.renderer().write("日本語");
+  ctx.renderer().move_to(1, 0);
+  ctx.renderer().write("ab");
+  ctx.renderer().flush();
+  assert_eq!(ctx.canvas().lines(), vec![" ab 語"]);
+}
+
+#[test]
+fn row_of_wide_children() {
+  let row = RowView::new().child("日本").child("x");
+  let mut driver = Driver::new(row, Size2D::new(6, 1));
+  assert_eq!(driver.lines(), vec!["日本x "]);
+}
+
+#[test]
+fn table_with_non_ascii_data() {
+  let table = Table::new()
+    .columns(vec![Column::new("HOST"), Column::new("FILE")])
+    .data(vec![vec!["サーバー", "résumé.txt"], vec!["🦀-box", "naïve.md"]]);
+  let mut driver = Driver::new(table, Size2D::new(24, 3));
+  assert_eq!(
+    driver.lines(),
+    vec![
+      "HOST     FILE           ",
+      "サーバー résumé.txt     ",
+      "🦀-box   naïve.md       "
+    ]
+  );
+}
@@ -0,0 +1,93 @@
+//! Derive macro for the witui `Widget` trait, re-exported by witui with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Index, Member};
+
+/// Implement `Widget` for a wrapper by forwarding to the field marked `#[child]`:
+/// layout, capabilities and scrolling are the child's, the child is rendered into the whole frame,
+/// and it is the `single_child`, so that the events reach it first.
+///
+/// ```ignore
+/// #[derive(Widget)]
+/// struct Card<Child> {
+///   title: String,
+///   #[child]
+///   content: Child,
+/// }
+/// ```
+#[proc_macro_derive(Widget, attributes(child))]
+pub fn derive_widget(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  match widget_impl(input) {
+    Ok(tokens) => tokens.into(),
+    Err(error) => error.to_compile_error().into(),
+  }
+}
+
+fn widget_impl(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+  let fields = match &input.data {
+    Data::Struct(data) => &data.fields,
+    _ => return Err(Error::new(input.span(), "Widget can only be derived for structs")),
+  };
+  let mut children = fields
+    .iter()
+    .enumerate()
+    .filter(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident("child")));
+  let (idx, field) = match (children.next(), children.next()) {
+    (Some(child), None) => child,
+    (Some(_), Some((_, second))) => return Err(Error::new(second.span(), "only one field can be the #[child]")),
+    (None, _) => return Err(Error::new(fields.span(), "mark the field to forward to with #[child]")),
+  };
+  let member = match (&field.ident, fields) {
+    (Some(ident), Fields::Named(_)) => Member::Named(ident.clone()),
+    _ => Member::Unnamed(Index::from(idx)),
+  };
+  let child_ty = field.ty.clone();
+
+  input
+    .generics
+    .make_where_clause()
+    .predicates
+    .push(parse_quote!(#child_ty: ::witui::widgets::Widget));
+  let name = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let child = member.to_token_stream();
+
+  Ok(quote! {
+    impl #impl_generics ::witui::widgets::Widget for #name #ty_generics #where_clause {
+      fn layout(
+        &self, avail_size: &::witui::__private::Size2D<usize>,
+      ) -> ::witui::widgets::LayoutResult {
+        ::witui::widgets::Widget::layout(&self.#child, avail_size)
+      }
+
+      fn render(&self, ctx: &::witui::render::RenderCtx) -> ::witui::widgets::RenderResult {
+        ctx.render_child_widget(*ctx.get_frame(), &self.#child)
+      }
+
+      fn scroll_position(
+        &self, orientation: ::witui::widgets::Orientation, size: &::witui::__private::Size2D<usize>,
+      ) -> Option<::witui::widgets::ScrollPosition> {
+        ::witui::widgets::Widget::scroll_position(&self.#child, orientation, size)
+      }
+
+      fn scroll_to(
+        &mut self, orientation: ::witui::widgets::Orientation, offset: usize,
+        size: &::witui::__private::Size2D<usize>,
+      ) {
+        ::witui::widgets::Widget::scroll_to(&mut self.#child, orientation, offset, size)
+      }
+
+      fn single_child(&self) -> Option<&dyn ::witui::widgets::Widget> {
+        Some(&self.#child)
+      }
+
+      fn single_child_mut(&mut self) -> Option<&mut dyn ::witui::widgets::Widget> {
+        Some(&mut self.#child)
+      }
+    }
+  })
+}